@@ -1,5 +1,5 @@
 #![allow(non_upper_case_globals)]
-#![feature(libc, std_misc)]
+#![feature(libc, std_misc, core)]
 /* workaround: the rust compiler doesn't recognize
    the feature std_misc yet, still it warns
    about using it */
@@ -8,6 +8,7 @@
 
 extern crate libc;
 use libc::{c_int, c_ulonglong, c_char, size_t};
+use std::simd::u64x2;
 
 // aead
 pub const crypto_aead_chacha20poly1305_KEYBYTES: usize = 32;
@@ -15,6 +16,32 @@ pub const crypto_aead_chacha20poly1305_NSECBYTES: usize = 0;
 pub const crypto_aead_chacha20poly1305_NPUBBYTES: usize = 8;
 pub const crypto_aead_chacha20poly1305_ABYTES: usize = 16;
 
+pub const crypto_aead_chacha20poly1305_ietf_KEYBYTES: usize = 32;
+pub const crypto_aead_chacha20poly1305_ietf_NSECBYTES: usize = 0;
+pub const crypto_aead_chacha20poly1305_ietf_NPUBBYTES: usize = 12;
+pub const crypto_aead_chacha20poly1305_ietf_ABYTES: usize = 16;
+
+pub const crypto_aead_xchacha20poly1305_ietf_KEYBYTES: usize = 32;
+pub const crypto_aead_xchacha20poly1305_ietf_NSECBYTES: usize = 0;
+pub const crypto_aead_xchacha20poly1305_ietf_NPUBBYTES: usize = 24;
+pub const crypto_aead_xchacha20poly1305_ietf_ABYTES: usize = 16;
+
+pub const crypto_aead_aes256gcm_KEYBYTES: usize = 32;
+pub const crypto_aead_aes256gcm_NSECBYTES: usize = 0;
+pub const crypto_aead_aes256gcm_NPUBBYTES: usize = 12;
+pub const crypto_aead_aes256gcm_ABYTES: usize = 16;
+pub const crypto_aead_aes256gcm_STATEBYTES: usize = 512;
+
+// crypto_aead_aes256gcm_state has to be 16-byte aligned, the zero-sized
+// u64x2 array makes sure the compiler aligns it in the same way as the C
+// compiler does.
+#[repr(C)]
+#[derive(Copy)]
+pub struct crypto_aead_aes256gcm_state {
+    pub _align: [u64x2; 0],
+    pub opaque: [u8; crypto_aead_aes256gcm_STATEBYTES],
+}
+
 // stream
 pub const crypto_stream_KEYBYTES: usize = crypto_stream_xsalsa20_KEYBYTES;
 pub const crypto_stream_NONCEBYTES: usize =
@@ -63,6 +90,14 @@ pub const crypto_onetimeauth_PRIMITIVE: &'static str =  "poly1305";
 
 pub const crypto_onetimeauth_poly1305_BYTES: usize = 16;
 pub const crypto_onetimeauth_poly1305_KEYBYTES: usize = 32;
+pub const crypto_onetimeauth_poly1305_STATEBYTES: usize = 256;
+
+#[repr(C)]
+#[derive(Copy)]
+pub struct crypto_onetimeauth_poly1305_state {
+    pub _align: [u64x2; 0],
+    pub opaque: [u8; crypto_onetimeauth_poly1305_STATEBYTES],
+}
 
 // pwhash
 // crypto_pwhash_scryptsalsa208sha256.h
@@ -77,6 +112,34 @@ pub const crypto_pwhash_scryptsalsa208sha256_OPSLIMIT_SENSITIVE: usize =
     33554432;
 pub const crypto_pwhash_scryptsalsa208sha256_MEMLIMIT_SENSITIVE: usize =
     1073741824;
+// crypto_pwhash_argon2i.h
+pub const crypto_pwhash_argon2i_ALG_ARGON2I13: c_int = 1;
+pub const crypto_pwhash_argon2i_SALTBYTES: usize = 16;
+pub const crypto_pwhash_argon2i_STRBYTES: usize = 128;
+pub const crypto_pwhash_argon2i_STRPREFIX: &'static str = "$argon2i$";
+pub const crypto_pwhash_argon2i_BYTES_MIN: usize = 16;
+pub const crypto_pwhash_argon2i_OPSLIMIT_MIN: usize = 3;
+pub const crypto_pwhash_argon2i_MEMLIMIT_MIN: usize = 8192;
+pub const crypto_pwhash_argon2i_OPSLIMIT_INTERACTIVE: usize = 4;
+pub const crypto_pwhash_argon2i_MEMLIMIT_INTERACTIVE: usize = 33554432;
+pub const crypto_pwhash_argon2i_OPSLIMIT_MODERATE: usize = 6;
+pub const crypto_pwhash_argon2i_MEMLIMIT_MODERATE: usize = 134217728;
+pub const crypto_pwhash_argon2i_OPSLIMIT_SENSITIVE: usize = 8;
+pub const crypto_pwhash_argon2i_MEMLIMIT_SENSITIVE: usize = 536870912;
+// crypto_pwhash_argon2id.h
+pub const crypto_pwhash_argon2id_ALG_ARGON2ID13: c_int = 2;
+pub const crypto_pwhash_argon2id_SALTBYTES: usize = 16;
+pub const crypto_pwhash_argon2id_STRBYTES: usize = 128;
+pub const crypto_pwhash_argon2id_STRPREFIX: &'static str = "$argon2id$";
+pub const crypto_pwhash_argon2id_BYTES_MIN: usize = 16;
+pub const crypto_pwhash_argon2id_OPSLIMIT_MIN: usize = 1;
+pub const crypto_pwhash_argon2id_MEMLIMIT_MIN: usize = 8192;
+pub const crypto_pwhash_argon2id_OPSLIMIT_INTERACTIVE: usize = 2;
+pub const crypto_pwhash_argon2id_MEMLIMIT_INTERACTIVE: usize = 67108864;
+pub const crypto_pwhash_argon2id_OPSLIMIT_MODERATE: usize = 3;
+pub const crypto_pwhash_argon2id_MEMLIMIT_MODERATE: usize = 268435456;
+pub const crypto_pwhash_argon2id_OPSLIMIT_SENSITIVE: usize = 4;
+pub const crypto_pwhash_argon2id_MEMLIMIT_SENSITIVE: usize = 1073741824;
 
 // hash
 pub const crypto_hash_BYTES: usize = crypto_hash_sha512_BYTES;
@@ -84,8 +147,63 @@ pub const crypto_hash_PRIMITIVE: &'static str = "sha512";
 
 pub const crypto_hash_sha256_BYTES: usize =  32;
 
+#[repr(C)]
+#[derive(Copy)]
+pub struct crypto_hash_sha256_state {
+    pub state: [u32; 8],
+    pub count: u64,
+    pub buf: [u8; 64],
+}
+
 pub const crypto_hash_sha512_BYTES: usize = 64;
 
+#[repr(C)]
+#[derive(Copy)]
+pub struct crypto_hash_sha512_state {
+    pub state: [u64; 8],
+    pub count: [u64; 2],
+    pub buf: [u8; 128],
+}
+
+// auth state
+#[repr(C)]
+#[derive(Copy)]
+pub struct crypto_auth_hmacsha256_state {
+    pub ictx: crypto_hash_sha256_state,
+    pub octx: crypto_hash_sha256_state,
+}
+
+#[repr(C)]
+#[derive(Copy)]
+pub struct crypto_auth_hmacsha512_state {
+    pub ictx: crypto_hash_sha512_state,
+    pub octx: crypto_hash_sha512_state,
+}
+
+pub type crypto_auth_hmacsha512256_state = crypto_auth_hmacsha512_state;
+
+// generichash
+// crypto_generichash_blake2b.h
+pub const crypto_generichash_blake2b_BYTES_MIN: usize = 16;
+pub const crypto_generichash_blake2b_BYTES_MAX: usize = 64;
+pub const crypto_generichash_blake2b_BYTES: usize = 32;
+pub const crypto_generichash_blake2b_KEYBYTES_MIN: usize = 16;
+pub const crypto_generichash_blake2b_KEYBYTES_MAX: usize = 64;
+pub const crypto_generichash_blake2b_KEYBYTES: usize = 32;
+pub const crypto_generichash_blake2b_SALTBYTES: usize = 16;
+pub const crypto_generichash_blake2b_PERSONALBYTES: usize = 16;
+pub const crypto_generichash_blake2b_STATEBYTES: usize = 384;
+
+// libsodium declares the state 64-byte aligned. Rust cannot express that
+// alignment, so users of this type have to place it at a 64-byte aligned
+// address themselves instead of relying on the alignment of the type.
+#[repr(C)]
+#[derive(Copy)]
+pub struct crypto_generichash_blake2b_state {
+    pub _align: [u64x2; 0],
+    pub opaque: [u8; crypto_generichash_blake2b_STATEBYTES],
+}
+
 // box
 pub const crypto_box_curve25519xsalsa20poly1305_SEEDBYTES: usize = 32;
 pub const crypto_box_curve25519xsalsa20poly1305_PUBLICKEYBYTES: usize = 32;
@@ -97,6 +215,15 @@ pub const crypto_box_curve25519xsalsa20poly1305_BOXZEROBYTES: usize = 16;
 pub const crypto_box_curve25519xsalsa20poly1305_MACBYTES: usize =
     crypto_box_curve25519xsalsa20poly1305_ZEROBYTES -
     crypto_box_curve25519xsalsa20poly1305_BOXZEROBYTES;
+pub const crypto_box_SEALBYTES: usize =
+    crypto_box_curve25519xsalsa20poly1305_PUBLICKEYBYTES +
+    crypto_box_curve25519xsalsa20poly1305_MACBYTES;
+pub const crypto_box_curve25519xchacha20poly1305_SEEDBYTES: usize = 32;
+pub const crypto_box_curve25519xchacha20poly1305_PUBLICKEYBYTES: usize = 32;
+pub const crypto_box_curve25519xchacha20poly1305_SECRETKEYBYTES: usize = 32;
+pub const crypto_box_curve25519xchacha20poly1305_BEFORENMBYTES: usize = 32;
+pub const crypto_box_curve25519xchacha20poly1305_NONCEBYTES: usize = 24;
+pub const crypto_box_curve25519xchacha20poly1305_MACBYTES: usize = 16;
 
 // scalarmult
 pub const crypto_scalarmult_curve25519_BYTES: usize = 32;
@@ -124,10 +251,35 @@ pub const crypto_secretbox_xsalsa20poly1305_BOXZEROBYTES: usize = 16;
 pub const crypto_secretbox_xsalsa20poly1305_MACBYTES: usize =
     crypto_secretbox_xsalsa20poly1305_ZEROBYTES -
     crypto_secretbox_xsalsa20poly1305_BOXZEROBYTES;
+pub const crypto_secretbox_xchacha20poly1305_KEYBYTES: usize = 32;
+pub const crypto_secretbox_xchacha20poly1305_NONCEBYTES: usize = 24;
+pub const crypto_secretbox_xchacha20poly1305_MACBYTES: usize = 16;
+
+// secretstream
+// crypto_secretstream_xchacha20poly1305.h
+pub const crypto_secretstream_xchacha20poly1305_ABYTES: usize = 17;
+pub const crypto_secretstream_xchacha20poly1305_HEADERBYTES: usize = 24;
+pub const crypto_secretstream_xchacha20poly1305_KEYBYTES: usize = 32;
+pub const crypto_secretstream_xchacha20poly1305_TAG_MESSAGE: u8 = 0;
+pub const crypto_secretstream_xchacha20poly1305_TAG_PUSH: u8 = 1;
+pub const crypto_secretstream_xchacha20poly1305_TAG_REKEY: u8 = 2;
+pub const crypto_secretstream_xchacha20poly1305_TAG_FINAL: u8 = 3;
+pub const crypto_secretstream_xchacha20poly1305_STATEBYTES: usize = 52;
+
+#[repr(C)]
+#[derive(Copy)]
+pub struct crypto_secretstream_xchacha20poly1305_state {
+    pub k: [u8; 32],
+    pub nonce: [u8; 12],
+    pub _pad: [u8; 8],
+}
 
 extern {
     // core.h
     pub fn sodium_init() -> c_int;
+
+    // utils.h
+    pub fn sodium_memcmp(b1: *const u8, b2: *const u8, len: size_t) -> c_int;
     
     // aead
     // crypto_aead_chacha20poly1305.h
@@ -155,6 +307,191 @@ extern {
         adlen: c_ulonglong,
         npub: *const [u8; crypto_aead_chacha20poly1305_NPUBBYTES],
         k: *const [u8; crypto_aead_chacha20poly1305_KEYBYTES]) -> c_int;
+    pub fn crypto_aead_chacha20poly1305_encrypt_detached(
+        c: *mut u8,
+        mac: *mut [u8; crypto_aead_chacha20poly1305_ABYTES],
+        maclen_p: *mut c_ulonglong,
+        m: *const u8,
+        mlen: c_ulonglong,
+        ad: *const u8,
+        adlen: c_ulonglong,
+        nsec: *const [u8; crypto_aead_chacha20poly1305_NSECBYTES],
+        npub: *const [u8; crypto_aead_chacha20poly1305_NPUBBYTES],
+        k: *const [u8; crypto_aead_chacha20poly1305_KEYBYTES]) -> c_int;
+    pub fn crypto_aead_chacha20poly1305_decrypt_detached(
+        m: *mut u8,
+        nsec: *mut [u8; crypto_aead_chacha20poly1305_NSECBYTES],
+        c: *const u8,
+        clen: c_ulonglong,
+        mac: *const [u8; crypto_aead_chacha20poly1305_ABYTES],
+        ad: *const u8,
+        adlen: c_ulonglong,
+        npub: *const [u8; crypto_aead_chacha20poly1305_NPUBBYTES],
+        k: *const [u8; crypto_aead_chacha20poly1305_KEYBYTES]) -> c_int;
+    pub fn crypto_aead_chacha20poly1305_ietf_keybytes() -> size_t;
+    pub fn crypto_aead_chacha20poly1305_ietf_nsecbytes() -> size_t;
+    pub fn crypto_aead_chacha20poly1305_ietf_npubbytes() -> size_t;
+    pub fn crypto_aead_chacha20poly1305_ietf_abytes() -> size_t;
+    pub fn crypto_aead_chacha20poly1305_ietf_encrypt(
+        c: *mut u8,
+        clen: *mut c_ulonglong,
+        m: *const u8,
+        mlen: c_ulonglong,
+        ad: *const u8,
+        adlen: c_ulonglong,
+        nsec: *const [u8; crypto_aead_chacha20poly1305_ietf_NSECBYTES],
+        npub: *const [u8; crypto_aead_chacha20poly1305_ietf_NPUBBYTES],
+        k: *const [u8; crypto_aead_chacha20poly1305_ietf_KEYBYTES]) -> c_int;
+    pub fn crypto_aead_chacha20poly1305_ietf_decrypt(
+        m: *mut u8,
+        mlen: *mut c_ulonglong,
+        nsec: *mut [u8; crypto_aead_chacha20poly1305_ietf_NSECBYTES],
+        c: *const u8,
+        clen: c_ulonglong,
+        ad: *const u8,
+        adlen: c_ulonglong,
+        npub: *const [u8; crypto_aead_chacha20poly1305_ietf_NPUBBYTES],
+        k: *const [u8; crypto_aead_chacha20poly1305_ietf_KEYBYTES]) -> c_int;
+    pub fn crypto_aead_chacha20poly1305_ietf_encrypt_detached(
+        c: *mut u8,
+        mac: *mut [u8; crypto_aead_chacha20poly1305_ietf_ABYTES],
+        maclen_p: *mut c_ulonglong,
+        m: *const u8,
+        mlen: c_ulonglong,
+        ad: *const u8,
+        adlen: c_ulonglong,
+        nsec: *const [u8; crypto_aead_chacha20poly1305_ietf_NSECBYTES],
+        npub: *const [u8; crypto_aead_chacha20poly1305_ietf_NPUBBYTES],
+        k: *const [u8; crypto_aead_chacha20poly1305_ietf_KEYBYTES]) -> c_int;
+    pub fn crypto_aead_chacha20poly1305_ietf_decrypt_detached(
+        m: *mut u8,
+        nsec: *mut [u8; crypto_aead_chacha20poly1305_ietf_NSECBYTES],
+        c: *const u8,
+        clen: c_ulonglong,
+        mac: *const [u8; crypto_aead_chacha20poly1305_ietf_ABYTES],
+        ad: *const u8,
+        adlen: c_ulonglong,
+        npub: *const [u8; crypto_aead_chacha20poly1305_ietf_NPUBBYTES],
+        k: *const [u8; crypto_aead_chacha20poly1305_ietf_KEYBYTES]) -> c_int;
+
+    // crypto_aead_xchacha20poly1305.h
+    pub fn crypto_aead_xchacha20poly1305_ietf_keybytes() -> size_t;
+    pub fn crypto_aead_xchacha20poly1305_ietf_nsecbytes() -> size_t;
+    pub fn crypto_aead_xchacha20poly1305_ietf_npubbytes() -> size_t;
+    pub fn crypto_aead_xchacha20poly1305_ietf_abytes() -> size_t;
+    pub fn crypto_aead_xchacha20poly1305_ietf_encrypt(
+        c: *mut u8,
+        clen: *mut c_ulonglong,
+        m: *const u8,
+        mlen: c_ulonglong,
+        ad: *const u8,
+        adlen: c_ulonglong,
+        nsec: *const [u8; crypto_aead_xchacha20poly1305_ietf_NSECBYTES],
+        npub: *const [u8; crypto_aead_xchacha20poly1305_ietf_NPUBBYTES],
+        k: *const [u8; crypto_aead_xchacha20poly1305_ietf_KEYBYTES]) -> c_int;
+    pub fn crypto_aead_xchacha20poly1305_ietf_decrypt(
+        m: *mut u8,
+        mlen: *mut c_ulonglong,
+        nsec: *mut [u8; crypto_aead_xchacha20poly1305_ietf_NSECBYTES],
+        c: *const u8,
+        clen: c_ulonglong,
+        ad: *const u8,
+        adlen: c_ulonglong,
+        npub: *const [u8; crypto_aead_xchacha20poly1305_ietf_NPUBBYTES],
+        k: *const [u8; crypto_aead_xchacha20poly1305_ietf_KEYBYTES]) -> c_int;
+    pub fn crypto_aead_xchacha20poly1305_ietf_encrypt_detached(
+        c: *mut u8,
+        mac: *mut [u8; crypto_aead_xchacha20poly1305_ietf_ABYTES],
+        maclen_p: *mut c_ulonglong,
+        m: *const u8,
+        mlen: c_ulonglong,
+        ad: *const u8,
+        adlen: c_ulonglong,
+        nsec: *const [u8; crypto_aead_xchacha20poly1305_ietf_NSECBYTES],
+        npub: *const [u8; crypto_aead_xchacha20poly1305_ietf_NPUBBYTES],
+        k: *const [u8; crypto_aead_xchacha20poly1305_ietf_KEYBYTES]) -> c_int;
+    pub fn crypto_aead_xchacha20poly1305_ietf_decrypt_detached(
+        m: *mut u8,
+        nsec: *mut [u8; crypto_aead_xchacha20poly1305_ietf_NSECBYTES],
+        c: *const u8,
+        clen: c_ulonglong,
+        mac: *const [u8; crypto_aead_xchacha20poly1305_ietf_ABYTES],
+        ad: *const u8,
+        adlen: c_ulonglong,
+        npub: *const [u8; crypto_aead_xchacha20poly1305_ietf_NPUBBYTES],
+        k: *const [u8; crypto_aead_xchacha20poly1305_ietf_KEYBYTES]) -> c_int;
+
+    // crypto_aead_aes256gcm.h
+    pub fn crypto_aead_aes256gcm_is_available() -> c_int;
+    pub fn crypto_aead_aes256gcm_keybytes() -> size_t;
+    pub fn crypto_aead_aes256gcm_nsecbytes() -> size_t;
+    pub fn crypto_aead_aes256gcm_npubbytes() -> size_t;
+    pub fn crypto_aead_aes256gcm_abytes() -> size_t;
+    pub fn crypto_aead_aes256gcm_statebytes() -> size_t;
+    pub fn crypto_aead_aes256gcm_encrypt(
+        c: *mut u8,
+        clen: *mut c_ulonglong,
+        m: *const u8,
+        mlen: c_ulonglong,
+        ad: *const u8,
+        adlen: c_ulonglong,
+        nsec: *const [u8; crypto_aead_aes256gcm_NSECBYTES],
+        npub: *const [u8; crypto_aead_aes256gcm_NPUBBYTES],
+        k: *const [u8; crypto_aead_aes256gcm_KEYBYTES]) -> c_int;
+    pub fn crypto_aead_aes256gcm_decrypt(
+        m: *mut u8,
+        mlen: *mut c_ulonglong,
+        nsec: *mut [u8; crypto_aead_aes256gcm_NSECBYTES],
+        c: *const u8,
+        clen: c_ulonglong,
+        ad: *const u8,
+        adlen: c_ulonglong,
+        npub: *const [u8; crypto_aead_aes256gcm_NPUBBYTES],
+        k: *const [u8; crypto_aead_aes256gcm_KEYBYTES]) -> c_int;
+    pub fn crypto_aead_aes256gcm_encrypt_detached(
+        c: *mut u8,
+        mac: *mut [u8; crypto_aead_aes256gcm_ABYTES],
+        maclen_p: *mut c_ulonglong,
+        m: *const u8,
+        mlen: c_ulonglong,
+        ad: *const u8,
+        adlen: c_ulonglong,
+        nsec: *const [u8; crypto_aead_aes256gcm_NSECBYTES],
+        npub: *const [u8; crypto_aead_aes256gcm_NPUBBYTES],
+        k: *const [u8; crypto_aead_aes256gcm_KEYBYTES]) -> c_int;
+    pub fn crypto_aead_aes256gcm_decrypt_detached(
+        m: *mut u8,
+        nsec: *mut [u8; crypto_aead_aes256gcm_NSECBYTES],
+        c: *const u8,
+        clen: c_ulonglong,
+        mac: *const [u8; crypto_aead_aes256gcm_ABYTES],
+        ad: *const u8,
+        adlen: c_ulonglong,
+        npub: *const [u8; crypto_aead_aes256gcm_NPUBBYTES],
+        k: *const [u8; crypto_aead_aes256gcm_KEYBYTES]) -> c_int;
+    pub fn crypto_aead_aes256gcm_beforenm(
+        ctx: *mut crypto_aead_aes256gcm_state,
+        k: *const [u8; crypto_aead_aes256gcm_KEYBYTES]) -> c_int;
+    pub fn crypto_aead_aes256gcm_encrypt_afternm(
+        c: *mut u8,
+        clen: *mut c_ulonglong,
+        m: *const u8,
+        mlen: c_ulonglong,
+        ad: *const u8,
+        adlen: c_ulonglong,
+        nsec: *const [u8; crypto_aead_aes256gcm_NSECBYTES],
+        npub: *const [u8; crypto_aead_aes256gcm_NPUBBYTES],
+        ctx: *const crypto_aead_aes256gcm_state) -> c_int;
+    pub fn crypto_aead_aes256gcm_decrypt_afternm(
+        m: *mut u8,
+        mlen: *mut c_ulonglong,
+        nsec: *mut [u8; crypto_aead_aes256gcm_NSECBYTES],
+        c: *const u8,
+        clen: c_ulonglong,
+        ad: *const u8,
+        adlen: c_ulonglong,
+        npub: *const [u8; crypto_aead_aes256gcm_NPUBBYTES],
+        ctx: *const crypto_aead_aes256gcm_state) -> c_int;
     
     // auth
     // crypto_auth.h
@@ -183,6 +520,18 @@ extern {
         m: *const u8,
         mlen: c_ulonglong,
         k: *const [u8; crypto_auth_hmacsha256_KEYBYTES]) -> c_int;
+    pub fn crypto_auth_hmacsha256_statebytes() -> size_t;
+    pub fn crypto_auth_hmacsha256_init(
+        state: *mut crypto_auth_hmacsha256_state,
+        key: *const u8,
+        keylen: size_t) -> c_int;
+    pub fn crypto_auth_hmacsha256_update(
+        state: *mut crypto_auth_hmacsha256_state,
+        m: *const u8,
+        mlen: c_ulonglong) -> c_int;
+    pub fn crypto_auth_hmacsha256_final(
+        state: *mut crypto_auth_hmacsha256_state,
+        a: *mut [u8; crypto_auth_hmacsha256_BYTES]) -> c_int;
     
     pub fn crypto_auth_hmacsha512(
         a: *mut [u8; crypto_auth_hmacsha512_BYTES],
@@ -196,6 +545,18 @@ extern {
         k: *const [u8; crypto_auth_hmacsha512_KEYBYTES]) -> c_int;
     pub fn crypto_auth_hmacsha512_bytes() -> size_t;
     pub fn crypto_auth_hmacsha512_keybytes() -> size_t;
+    pub fn crypto_auth_hmacsha512_statebytes() -> size_t;
+    pub fn crypto_auth_hmacsha512_init(
+        state: *mut crypto_auth_hmacsha512_state,
+        key: *const u8,
+        keylen: size_t) -> c_int;
+    pub fn crypto_auth_hmacsha512_update(
+        state: *mut crypto_auth_hmacsha512_state,
+        m: *const u8,
+        mlen: c_ulonglong) -> c_int;
+    pub fn crypto_auth_hmacsha512_final(
+        state: *mut crypto_auth_hmacsha512_state,
+        a: *mut [u8; crypto_auth_hmacsha512_BYTES]) -> c_int;
     
     pub fn crypto_auth_hmacsha512256(
         a: *mut [u8; crypto_auth_hmacsha512256_BYTES],
@@ -209,6 +570,18 @@ extern {
         k: *const [u8; crypto_auth_hmacsha512256_KEYBYTES]) -> c_int;
     pub fn crypto_auth_hmacsha512256_bytes() -> size_t;
     pub fn crypto_auth_hmacsha512256_keybytes() -> size_t;
+    pub fn crypto_auth_hmacsha512256_statebytes() -> size_t;
+    pub fn crypto_auth_hmacsha512256_init(
+        state: *mut crypto_auth_hmacsha512256_state,
+        key: *const u8,
+        keylen: size_t) -> c_int;
+    pub fn crypto_auth_hmacsha512256_update(
+        state: *mut crypto_auth_hmacsha512256_state,
+        m: *const u8,
+        mlen: c_ulonglong) -> c_int;
+    pub fn crypto_auth_hmacsha512256_final(
+        state: *mut crypto_auth_hmacsha512256_state,
+        a: *mut [u8; crypto_auth_hmacsha512256_BYTES]) -> c_int;
     
     // onetimeauth
     pub fn crypto_onetimeauth_bytes() -> size_t;
@@ -227,6 +600,17 @@ extern {
         k: *const [u8; crypto_onetimeauth_poly1305_KEYBYTES]) -> c_int;
     pub fn crypto_onetimeauth_poly1305_bytes() -> size_t;
     pub fn crypto_onetimeauth_poly1305_keybytes() -> size_t;
+    pub fn crypto_onetimeauth_poly1305_statebytes() -> size_t;
+    pub fn crypto_onetimeauth_poly1305_init(
+        state: *mut crypto_onetimeauth_poly1305_state,
+        k: *const [u8; crypto_onetimeauth_poly1305_KEYBYTES]) -> c_int;
+    pub fn crypto_onetimeauth_poly1305_update(
+        state: *mut crypto_onetimeauth_poly1305_state,
+        m: *const u8,
+        mlen: c_ulonglong) -> c_int;
+    pub fn crypto_onetimeauth_poly1305_final(
+        state: *mut crypto_onetimeauth_poly1305_state,
+        a: *mut [u8; crypto_onetimeauth_poly1305_BYTES]) -> c_int;
     
     // pwhash
     // crypto_pwhash_scryptsalsa208sha256.h
@@ -267,6 +651,72 @@ extern {
         p: u32,
         buf: *mut u8,
         buflen: size_t) -> c_int;
+    // crypto_pwhash_argon2i.h
+    pub fn crypto_pwhash_argon2i_alg_argon2i13() -> c_int;
+    pub fn crypto_pwhash_argon2i_saltbytes() -> size_t;
+    pub fn crypto_pwhash_argon2i_strbytes() -> size_t;
+    pub fn crypto_pwhash_argon2i_strprefix() -> *const c_char;
+    pub fn crypto_pwhash_argon2i_bytes_min() -> size_t;
+    pub fn crypto_pwhash_argon2i_opslimit_min() -> size_t;
+    pub fn crypto_pwhash_argon2i_memlimit_min() -> size_t;
+    pub fn crypto_pwhash_argon2i_opslimit_interactive() -> size_t;
+    pub fn crypto_pwhash_argon2i_memlimit_interactive() -> size_t;
+    pub fn crypto_pwhash_argon2i_opslimit_moderate() -> size_t;
+    pub fn crypto_pwhash_argon2i_memlimit_moderate() -> size_t;
+    pub fn crypto_pwhash_argon2i_opslimit_sensitive() -> size_t;
+    pub fn crypto_pwhash_argon2i_memlimit_sensitive() -> size_t;
+    pub fn crypto_pwhash_argon2i(
+        out: *mut u8,
+        outlen: c_ulonglong,
+        passwd: *const c_char,
+        passwdlen: c_ulonglong,
+        salt: *const [u8; crypto_pwhash_argon2i_SALTBYTES],
+        opslimit: c_ulonglong,
+        memlimit: size_t,
+        alg: c_int) -> c_int;
+    pub fn crypto_pwhash_argon2i_str(
+        out: *mut [c_char; crypto_pwhash_argon2i_STRBYTES],
+        passwd: *const c_char,
+        passwdlen: c_ulonglong,
+        opslimit: c_ulonglong,
+        memlimit: size_t) -> c_int;
+    pub fn crypto_pwhash_argon2i_str_verify(
+        str_: *const [c_char; crypto_pwhash_argon2i_STRBYTES],
+        passwd: *const c_char,
+        passwdlen: c_ulonglong) -> c_int;
+    // crypto_pwhash_argon2id.h
+    pub fn crypto_pwhash_argon2id_alg_argon2id13() -> c_int;
+    pub fn crypto_pwhash_argon2id_saltbytes() -> size_t;
+    pub fn crypto_pwhash_argon2id_strbytes() -> size_t;
+    pub fn crypto_pwhash_argon2id_strprefix() -> *const c_char;
+    pub fn crypto_pwhash_argon2id_bytes_min() -> size_t;
+    pub fn crypto_pwhash_argon2id_opslimit_min() -> size_t;
+    pub fn crypto_pwhash_argon2id_memlimit_min() -> size_t;
+    pub fn crypto_pwhash_argon2id_opslimit_interactive() -> size_t;
+    pub fn crypto_pwhash_argon2id_memlimit_interactive() -> size_t;
+    pub fn crypto_pwhash_argon2id_opslimit_moderate() -> size_t;
+    pub fn crypto_pwhash_argon2id_memlimit_moderate() -> size_t;
+    pub fn crypto_pwhash_argon2id_opslimit_sensitive() -> size_t;
+    pub fn crypto_pwhash_argon2id_memlimit_sensitive() -> size_t;
+    pub fn crypto_pwhash_argon2id(
+        out: *mut u8,
+        outlen: c_ulonglong,
+        passwd: *const c_char,
+        passwdlen: c_ulonglong,
+        salt: *const [u8; crypto_pwhash_argon2id_SALTBYTES],
+        opslimit: c_ulonglong,
+        memlimit: size_t,
+        alg: c_int) -> c_int;
+    pub fn crypto_pwhash_argon2id_str(
+        out: *mut [c_char; crypto_pwhash_argon2id_STRBYTES],
+        passwd: *const c_char,
+        passwdlen: c_ulonglong,
+        opslimit: c_ulonglong,
+        memlimit: size_t) -> c_int;
+    pub fn crypto_pwhash_argon2id_str_verify(
+        str_: *const [c_char; crypto_pwhash_argon2id_STRBYTES],
+        passwd: *const c_char,
+        passwdlen: c_ulonglong) -> c_int;
     
     // stream
     pub fn crypto_stream_keybytes() -> size_t;
@@ -302,6 +752,13 @@ extern {
         mlen: c_ulonglong,
         n: *const [u8; crypto_stream_salsa20_NONCEBYTES],
         k: *const [u8; crypto_stream_salsa20_KEYBYTES]) -> c_int;
+    pub fn crypto_stream_salsa20_xor_ic(
+        c: *mut u8,
+        m: *const u8,
+        mlen: c_ulonglong,
+        n: *const [u8; crypto_stream_salsa20_NONCEBYTES],
+        ic: u64,
+        k: *const [u8; crypto_stream_salsa20_KEYBYTES]) -> c_int;
     pub fn crypto_stream_salsa20_keybytes() -> size_t;
     pub fn crypto_stream_salsa20_noncebytes() -> size_t;
     
@@ -344,6 +801,13 @@ extern {
         mlen: c_ulonglong,
         n: *const [u8; crypto_stream_xsalsa20_NONCEBYTES],
         k: *const [u8; crypto_stream_xsalsa20_KEYBYTES]) -> c_int;
+    pub fn crypto_stream_xsalsa20_xor_ic(
+        c: *mut u8,
+        m: *const u8,
+        mlen: c_ulonglong,
+        n: *const [u8; crypto_stream_xsalsa20_NONCEBYTES],
+        ic: u64,
+        k: *const [u8; crypto_stream_xsalsa20_KEYBYTES]) -> c_int;
     pub fn crypto_stream_xsalsa20_keybytes() -> size_t;
     pub fn crypto_stream_xsalsa20_noncebytes() -> size_t;
     
@@ -358,11 +822,73 @@ extern {
                               m: *const u8,
                               mlen: c_ulonglong) -> c_int;
     pub fn crypto_hash_sha256_bytes() -> size_t;
+    pub fn crypto_hash_sha256_statebytes() -> size_t;
+    pub fn crypto_hash_sha256_init(state: *mut crypto_hash_sha256_state) -> c_int;
+    pub fn crypto_hash_sha256_update(state: *mut crypto_hash_sha256_state,
+                                     m: *const u8,
+                                     mlen: c_ulonglong) -> c_int;
+    pub fn crypto_hash_sha256_final(state: *mut crypto_hash_sha256_state,
+                                    h: *mut [u8; crypto_hash_sha256_BYTES]) -> c_int;
     
     pub fn crypto_hash_sha512(h: *mut [u8; crypto_hash_sha512_BYTES],
                               m: *const u8,
                               mlen: c_ulonglong) -> c_int;
     pub fn crypto_hash_sha512_bytes() -> size_t;
+    pub fn crypto_hash_sha512_statebytes() -> size_t;
+    pub fn crypto_hash_sha512_init(state: *mut crypto_hash_sha512_state) -> c_int;
+    pub fn crypto_hash_sha512_update(state: *mut crypto_hash_sha512_state,
+                                     m: *const u8,
+                                     mlen: c_ulonglong) -> c_int;
+    pub fn crypto_hash_sha512_final(state: *mut crypto_hash_sha512_state,
+                                    h: *mut [u8; crypto_hash_sha512_BYTES]) -> c_int;
+
+    // generichash
+    // crypto_generichash_blake2b.h
+    pub fn crypto_generichash_blake2b_bytes_min() -> size_t;
+    pub fn crypto_generichash_blake2b_bytes_max() -> size_t;
+    pub fn crypto_generichash_blake2b_bytes() -> size_t;
+    pub fn crypto_generichash_blake2b_keybytes_min() -> size_t;
+    pub fn crypto_generichash_blake2b_keybytes_max() -> size_t;
+    pub fn crypto_generichash_blake2b_keybytes() -> size_t;
+    pub fn crypto_generichash_blake2b_saltbytes() -> size_t;
+    pub fn crypto_generichash_blake2b_personalbytes() -> size_t;
+    pub fn crypto_generichash_blake2b_statebytes() -> size_t;
+    pub fn crypto_generichash_blake2b(
+        out: *mut u8,
+        outlen: size_t,
+        m: *const u8,
+        mlen: c_ulonglong,
+        key: *const u8,
+        keylen: size_t) -> c_int;
+    pub fn crypto_generichash_blake2b_salt_personal(
+        out: *mut u8,
+        outlen: size_t,
+        m: *const u8,
+        mlen: c_ulonglong,
+        key: *const u8,
+        keylen: size_t,
+        salt: *const [u8; crypto_generichash_blake2b_SALTBYTES],
+        personal: *const [u8; crypto_generichash_blake2b_PERSONALBYTES]) -> c_int;
+    pub fn crypto_generichash_blake2b_init(
+        state: *mut crypto_generichash_blake2b_state,
+        key: *const u8,
+        keylen: size_t,
+        outlen: size_t) -> c_int;
+    pub fn crypto_generichash_blake2b_init_salt_personal(
+        state: *mut crypto_generichash_blake2b_state,
+        key: *const u8,
+        keylen: size_t,
+        outlen: size_t,
+        salt: *const [u8; crypto_generichash_blake2b_SALTBYTES],
+        personal: *const [u8; crypto_generichash_blake2b_PERSONALBYTES]) -> c_int;
+    pub fn crypto_generichash_blake2b_update(
+        state: *mut crypto_generichash_blake2b_state,
+        m: *const u8,
+        mlen: c_ulonglong) -> c_int;
+    pub fn crypto_generichash_blake2b_final(
+        state: *mut crypto_generichash_blake2b_state,
+        out: *mut u8,
+        outlen: size_t) -> c_int;
     
     // scalarmult
     pub fn crypto_scalarmult_curve25519(
@@ -423,6 +949,172 @@ extern {
     pub fn crypto_box_curve25519xsalsa20poly1305_zerobytes() -> size_t;
     pub fn crypto_box_curve25519xsalsa20poly1305_boxzerobytes() -> size_t;
     pub fn crypto_box_curve25519xsalsa20poly1305_macbytes() -> size_t;
+    pub fn crypto_box_easy(
+        c: *mut u8,
+        m: *const u8,
+        mlen: c_ulonglong,
+        n: *const [u8; crypto_box_curve25519xsalsa20poly1305_NONCEBYTES],
+        pk: *const [u8; crypto_box_curve25519xsalsa20poly1305_PUBLICKEYBYTES],
+        sk: *const [u8; crypto_box_curve25519xsalsa20poly1305_SECRETKEYBYTES])
+        -> c_int;
+    pub fn crypto_box_open_easy(
+        m: *mut u8,
+        c: *const u8,
+        clen: c_ulonglong,
+        n: *const [u8; crypto_box_curve25519xsalsa20poly1305_NONCEBYTES],
+        pk: *const [u8; crypto_box_curve25519xsalsa20poly1305_PUBLICKEYBYTES],
+        sk: *const [u8; crypto_box_curve25519xsalsa20poly1305_SECRETKEYBYTES])
+        -> c_int;
+    pub fn crypto_box_easy_afternm(
+        c: *mut u8,
+        m: *const u8,
+        mlen: c_ulonglong,
+        n: *const [u8; crypto_box_curve25519xsalsa20poly1305_NONCEBYTES],
+        k: *const [u8; crypto_box_curve25519xsalsa20poly1305_BEFORENMBYTES])
+        -> c_int;
+    pub fn crypto_box_open_easy_afternm(
+        m: *mut u8,
+        c: *const u8,
+        clen: c_ulonglong,
+        n: *const [u8; crypto_box_curve25519xsalsa20poly1305_NONCEBYTES],
+        k: *const [u8; crypto_box_curve25519xsalsa20poly1305_BEFORENMBYTES])
+        -> c_int;
+    pub fn crypto_box_detached(
+        c: *mut u8,
+        mac: *mut [u8; crypto_box_curve25519xsalsa20poly1305_MACBYTES],
+        m: *const u8,
+        mlen: c_ulonglong,
+        n: *const [u8; crypto_box_curve25519xsalsa20poly1305_NONCEBYTES],
+        pk: *const [u8; crypto_box_curve25519xsalsa20poly1305_PUBLICKEYBYTES],
+        sk: *const [u8; crypto_box_curve25519xsalsa20poly1305_SECRETKEYBYTES])
+        -> c_int;
+    pub fn crypto_box_open_detached(
+        m: *mut u8,
+        c: *const u8,
+        mac: *const [u8; crypto_box_curve25519xsalsa20poly1305_MACBYTES],
+        clen: c_ulonglong,
+        n: *const [u8; crypto_box_curve25519xsalsa20poly1305_NONCEBYTES],
+        pk: *const [u8; crypto_box_curve25519xsalsa20poly1305_PUBLICKEYBYTES],
+        sk: *const [u8; crypto_box_curve25519xsalsa20poly1305_SECRETKEYBYTES])
+        -> c_int;
+    pub fn crypto_box_detached_afternm(
+        c: *mut u8,
+        mac: *mut [u8; crypto_box_curve25519xsalsa20poly1305_MACBYTES],
+        m: *const u8,
+        mlen: c_ulonglong,
+        n: *const [u8; crypto_box_curve25519xsalsa20poly1305_NONCEBYTES],
+        k: *const [u8; crypto_box_curve25519xsalsa20poly1305_BEFORENMBYTES])
+        -> c_int;
+    pub fn crypto_box_open_detached_afternm(
+        m: *mut u8,
+        c: *const u8,
+        mac: *const [u8; crypto_box_curve25519xsalsa20poly1305_MACBYTES],
+        clen: c_ulonglong,
+        n: *const [u8; crypto_box_curve25519xsalsa20poly1305_NONCEBYTES],
+        k: *const [u8; crypto_box_curve25519xsalsa20poly1305_BEFORENMBYTES])
+        -> c_int;
+
+    // crypto_box_curve25519xchacha20poly1305.h
+    pub fn crypto_box_curve25519xchacha20poly1305_seedbytes() -> size_t;
+    pub fn crypto_box_curve25519xchacha20poly1305_publickeybytes() -> size_t;
+    pub fn crypto_box_curve25519xchacha20poly1305_secretkeybytes() -> size_t;
+    pub fn crypto_box_curve25519xchacha20poly1305_beforenmbytes() -> size_t;
+    pub fn crypto_box_curve25519xchacha20poly1305_noncebytes() -> size_t;
+    pub fn crypto_box_curve25519xchacha20poly1305_macbytes() -> size_t;
+    pub fn crypto_box_curve25519xchacha20poly1305_keypair(
+        pk: *mut [u8; crypto_box_curve25519xchacha20poly1305_PUBLICKEYBYTES],
+        sk: *mut [u8; crypto_box_curve25519xchacha20poly1305_SECRETKEYBYTES])
+        -> c_int;
+    pub fn crypto_box_curve25519xchacha20poly1305_seed_keypair(
+        pk: *mut [u8; crypto_box_curve25519xchacha20poly1305_PUBLICKEYBYTES],
+        sk: *mut [u8; crypto_box_curve25519xchacha20poly1305_SECRETKEYBYTES],
+        seed: *const [u8; crypto_box_curve25519xchacha20poly1305_SEEDBYTES])
+        -> c_int;
+    pub fn crypto_box_curve25519xchacha20poly1305_beforenm(
+        k: *mut [u8; crypto_box_curve25519xchacha20poly1305_BEFORENMBYTES],
+        pk: *const [u8; crypto_box_curve25519xchacha20poly1305_PUBLICKEYBYTES],
+        sk: *const [u8; crypto_box_curve25519xchacha20poly1305_SECRETKEYBYTES])
+        -> c_int;
+    pub fn crypto_box_curve25519xchacha20poly1305_easy(
+        c: *mut u8,
+        m: *const u8,
+        mlen: c_ulonglong,
+        n: *const [u8; crypto_box_curve25519xchacha20poly1305_NONCEBYTES],
+        pk: *const [u8; crypto_box_curve25519xchacha20poly1305_PUBLICKEYBYTES],
+        sk: *const [u8; crypto_box_curve25519xchacha20poly1305_SECRETKEYBYTES])
+        -> c_int;
+    pub fn crypto_box_curve25519xchacha20poly1305_open_easy(
+        m: *mut u8,
+        c: *const u8,
+        clen: c_ulonglong,
+        n: *const [u8; crypto_box_curve25519xchacha20poly1305_NONCEBYTES],
+        pk: *const [u8; crypto_box_curve25519xchacha20poly1305_PUBLICKEYBYTES],
+        sk: *const [u8; crypto_box_curve25519xchacha20poly1305_SECRETKEYBYTES])
+        -> c_int;
+    pub fn crypto_box_curve25519xchacha20poly1305_detached(
+        c: *mut u8,
+        mac: *mut [u8; crypto_box_curve25519xchacha20poly1305_MACBYTES],
+        m: *const u8,
+        mlen: c_ulonglong,
+        n: *const [u8; crypto_box_curve25519xchacha20poly1305_NONCEBYTES],
+        pk: *const [u8; crypto_box_curve25519xchacha20poly1305_PUBLICKEYBYTES],
+        sk: *const [u8; crypto_box_curve25519xchacha20poly1305_SECRETKEYBYTES])
+        -> c_int;
+    pub fn crypto_box_curve25519xchacha20poly1305_open_detached(
+        m: *mut u8,
+        c: *const u8,
+        mac: *const [u8; crypto_box_curve25519xchacha20poly1305_MACBYTES],
+        clen: c_ulonglong,
+        n: *const [u8; crypto_box_curve25519xchacha20poly1305_NONCEBYTES],
+        pk: *const [u8; crypto_box_curve25519xchacha20poly1305_PUBLICKEYBYTES],
+        sk: *const [u8; crypto_box_curve25519xchacha20poly1305_SECRETKEYBYTES])
+        -> c_int;
+    pub fn crypto_box_curve25519xchacha20poly1305_easy_afternm(
+        c: *mut u8,
+        m: *const u8,
+        mlen: c_ulonglong,
+        n: *const [u8; crypto_box_curve25519xchacha20poly1305_NONCEBYTES],
+        k: *const [u8; crypto_box_curve25519xchacha20poly1305_BEFORENMBYTES])
+        -> c_int;
+    pub fn crypto_box_curve25519xchacha20poly1305_open_easy_afternm(
+        m: *mut u8,
+        c: *const u8,
+        clen: c_ulonglong,
+        n: *const [u8; crypto_box_curve25519xchacha20poly1305_NONCEBYTES],
+        k: *const [u8; crypto_box_curve25519xchacha20poly1305_BEFORENMBYTES])
+        -> c_int;
+    pub fn crypto_box_curve25519xchacha20poly1305_detached_afternm(
+        c: *mut u8,
+        mac: *mut [u8; crypto_box_curve25519xchacha20poly1305_MACBYTES],
+        m: *const u8,
+        mlen: c_ulonglong,
+        n: *const [u8; crypto_box_curve25519xchacha20poly1305_NONCEBYTES],
+        k: *const [u8; crypto_box_curve25519xchacha20poly1305_BEFORENMBYTES])
+        -> c_int;
+    pub fn crypto_box_curve25519xchacha20poly1305_open_detached_afternm(
+        m: *mut u8,
+        c: *const u8,
+        mac: *const [u8; crypto_box_curve25519xchacha20poly1305_MACBYTES],
+        clen: c_ulonglong,
+        n: *const [u8; crypto_box_curve25519xchacha20poly1305_NONCEBYTES],
+        k: *const [u8; crypto_box_curve25519xchacha20poly1305_BEFORENMBYTES])
+        -> c_int;
+
+    // sealed box
+    pub fn crypto_box_seal(
+        c: *mut u8,
+        m: *const u8,
+        mlen: c_ulonglong,
+        pk: *const [u8; crypto_box_curve25519xsalsa20poly1305_PUBLICKEYBYTES])
+        -> c_int;
+    pub fn crypto_box_seal_open(
+        m: *mut u8,
+        c: *const u8,
+        clen: c_ulonglong,
+        pk: *const [u8; crypto_box_curve25519xsalsa20poly1305_PUBLICKEYBYTES],
+        sk: *const [u8; crypto_box_curve25519xsalsa20poly1305_SECRETKEYBYTES])
+        -> c_int;
+    pub fn crypto_box_sealbytes() -> size_t;
     
     // sign
     pub fn crypto_sign_ed25519_keypair(
@@ -494,6 +1186,7 @@ extern {
     // verify
     pub fn crypto_verify_16(x: *const u8, y: *const u8) -> c_int;
     pub fn crypto_verify_32(x: *const u8, y: *const u8) -> c_int;
+    pub fn crypto_verify_64(x: *const u8, y: *const u8) -> c_int;
 
     // secretbox
     pub fn crypto_secretbox_xsalsa20poly1305(
@@ -513,7 +1206,104 @@ extern {
     pub fn crypto_secretbox_xsalsa20poly1305_zerobytes() -> size_t;
     pub fn crypto_secretbox_xsalsa20poly1305_boxzerobytes() -> size_t;
     pub fn crypto_secretbox_xsalsa20poly1305_macbytes() -> size_t;
-    
+    pub fn crypto_secretbox_detached(
+        c: *mut u8,
+        mac: *mut [u8; crypto_secretbox_xsalsa20poly1305_MACBYTES],
+        m: *const u8,
+        mlen: c_ulonglong,
+        n: *const [u8; crypto_secretbox_xsalsa20poly1305_NONCEBYTES],
+        k: *const [u8; crypto_secretbox_xsalsa20poly1305_KEYBYTES]) -> c_int;
+    pub fn crypto_secretbox_easy(
+        c: *mut u8,
+        m: *const u8,
+        mlen: c_ulonglong,
+        n: *const [u8; crypto_secretbox_xsalsa20poly1305_NONCEBYTES],
+        k: *const [u8; crypto_secretbox_xsalsa20poly1305_KEYBYTES]) -> c_int;
+    pub fn crypto_secretbox_open_easy(
+        m: *mut u8,
+        c: *const u8,
+        clen: c_ulonglong,
+        n: *const [u8; crypto_secretbox_xsalsa20poly1305_NONCEBYTES],
+        k: *const [u8; crypto_secretbox_xsalsa20poly1305_KEYBYTES]) -> c_int;
+    pub fn crypto_secretbox_open_detached(
+        m: *mut u8,
+        c: *const u8,
+        mac: *const [u8; crypto_secretbox_xsalsa20poly1305_MACBYTES],
+        clen: c_ulonglong,
+        n: *const [u8; crypto_secretbox_xsalsa20poly1305_NONCEBYTES],
+        k: *const [u8; crypto_secretbox_xsalsa20poly1305_KEYBYTES]) -> c_int;
+    pub fn crypto_secretbox_xchacha20poly1305_easy(
+        c: *mut u8,
+        m: *const u8,
+        mlen: c_ulonglong,
+        n: *const [u8; crypto_secretbox_xchacha20poly1305_NONCEBYTES],
+        k: *const [u8; crypto_secretbox_xchacha20poly1305_KEYBYTES]) -> c_int;
+    pub fn crypto_secretbox_xchacha20poly1305_open_easy(
+        m: *mut u8,
+        c: *const u8,
+        clen: c_ulonglong,
+        n: *const [u8; crypto_secretbox_xchacha20poly1305_NONCEBYTES],
+        k: *const [u8; crypto_secretbox_xchacha20poly1305_KEYBYTES]) -> c_int;
+    pub fn crypto_secretbox_xchacha20poly1305_detached(
+        c: *mut u8,
+        mac: *mut [u8; crypto_secretbox_xchacha20poly1305_MACBYTES],
+        m: *const u8,
+        mlen: c_ulonglong,
+        n: *const [u8; crypto_secretbox_xchacha20poly1305_NONCEBYTES],
+        k: *const [u8; crypto_secretbox_xchacha20poly1305_KEYBYTES]) -> c_int;
+    pub fn crypto_secretbox_xchacha20poly1305_open_detached(
+        m: *mut u8,
+        c: *const u8,
+        mac: *const [u8; crypto_secretbox_xchacha20poly1305_MACBYTES],
+        clen: c_ulonglong,
+        n: *const [u8; crypto_secretbox_xchacha20poly1305_NONCEBYTES],
+        k: *const [u8; crypto_secretbox_xchacha20poly1305_KEYBYTES]) -> c_int;
+    pub fn crypto_secretbox_xchacha20poly1305_keybytes() -> size_t;
+    pub fn crypto_secretbox_xchacha20poly1305_noncebytes() -> size_t;
+    pub fn crypto_secretbox_xchacha20poly1305_macbytes() -> size_t;
+
+    // secretstream
+    // crypto_secretstream_xchacha20poly1305.h
+    pub fn crypto_secretstream_xchacha20poly1305_abytes() -> size_t;
+    pub fn crypto_secretstream_xchacha20poly1305_headerbytes() -> size_t;
+    pub fn crypto_secretstream_xchacha20poly1305_keybytes() -> size_t;
+    pub fn crypto_secretstream_xchacha20poly1305_messagebytes_max() -> size_t;
+    pub fn crypto_secretstream_xchacha20poly1305_statebytes() -> size_t;
+    pub fn crypto_secretstream_xchacha20poly1305_tag_message() -> u8;
+    pub fn crypto_secretstream_xchacha20poly1305_tag_push() -> u8;
+    pub fn crypto_secretstream_xchacha20poly1305_tag_rekey() -> u8;
+    pub fn crypto_secretstream_xchacha20poly1305_tag_final() -> u8;
+    pub fn crypto_secretstream_xchacha20poly1305_keygen(
+        k: *mut [u8; crypto_secretstream_xchacha20poly1305_KEYBYTES]);
+    pub fn crypto_secretstream_xchacha20poly1305_init_push(
+        state: *mut crypto_secretstream_xchacha20poly1305_state,
+        header: *mut [u8; crypto_secretstream_xchacha20poly1305_HEADERBYTES],
+        k: *const [u8; crypto_secretstream_xchacha20poly1305_KEYBYTES]) -> c_int;
+    pub fn crypto_secretstream_xchacha20poly1305_push(
+        state: *mut crypto_secretstream_xchacha20poly1305_state,
+        c: *mut u8,
+        clen_p: *mut c_ulonglong,
+        m: *const u8,
+        mlen: c_ulonglong,
+        ad: *const u8,
+        adlen: c_ulonglong,
+        tag: u8) -> c_int;
+    pub fn crypto_secretstream_xchacha20poly1305_init_pull(
+        state: *mut crypto_secretstream_xchacha20poly1305_state,
+        header: *const [u8; crypto_secretstream_xchacha20poly1305_HEADERBYTES],
+        k: *const [u8; crypto_secretstream_xchacha20poly1305_KEYBYTES]) -> c_int;
+    pub fn crypto_secretstream_xchacha20poly1305_pull(
+        state: *mut crypto_secretstream_xchacha20poly1305_state,
+        m: *mut u8,
+        mlen_p: *mut c_ulonglong,
+        tag_p: *mut u8,
+        c: *const u8,
+        clen: c_ulonglong,
+        ad: *const u8,
+        adlen: c_ulonglong) -> c_int;
+    pub fn crypto_secretstream_xchacha20poly1305_rekey(
+        state: *mut crypto_secretstream_xchacha20poly1305_state);
+
     // randombytes.h
     pub fn randombytes_buf(buf: *mut u8,
                            size: size_t);
@@ -540,6 +1330,84 @@ fn test_crypto_aead_chacha20poly1305_abytes() {
     assert!(unsafe { crypto_aead_chacha20poly1305_abytes() as usize } ==
             crypto_aead_chacha20poly1305_ABYTES)
 }
+#[test]
+fn test_crypto_aead_chacha20poly1305_ietf_keybytes() {
+    assert!(unsafe {
+        crypto_aead_chacha20poly1305_ietf_keybytes() as usize
+    } == crypto_aead_chacha20poly1305_ietf_KEYBYTES)
+}
+#[test]
+fn test_crypto_aead_chacha20poly1305_ietf_nsecbytes() {
+    assert!(unsafe {
+        crypto_aead_chacha20poly1305_ietf_nsecbytes() as usize
+    } == crypto_aead_chacha20poly1305_ietf_NSECBYTES)
+}
+#[test]
+fn test_crypto_aead_chacha20poly1305_ietf_npubbytes() {
+    assert!(unsafe {
+        crypto_aead_chacha20poly1305_ietf_npubbytes() as usize
+    } == crypto_aead_chacha20poly1305_ietf_NPUBBYTES)
+}
+#[test]
+fn test_crypto_aead_chacha20poly1305_ietf_abytes() {
+    assert!(unsafe {
+        crypto_aead_chacha20poly1305_ietf_abytes() as usize
+    } == crypto_aead_chacha20poly1305_ietf_ABYTES)
+}
+#[test]
+fn test_crypto_aead_xchacha20poly1305_ietf_keybytes() {
+    assert!(unsafe {
+        crypto_aead_xchacha20poly1305_ietf_keybytes() as usize
+    } == crypto_aead_xchacha20poly1305_ietf_KEYBYTES)
+}
+#[test]
+fn test_crypto_aead_xchacha20poly1305_ietf_nsecbytes() {
+    assert!(unsafe {
+        crypto_aead_xchacha20poly1305_ietf_nsecbytes() as usize
+    } == crypto_aead_xchacha20poly1305_ietf_NSECBYTES)
+}
+#[test]
+fn test_crypto_aead_xchacha20poly1305_ietf_npubbytes() {
+    assert!(unsafe {
+        crypto_aead_xchacha20poly1305_ietf_npubbytes() as usize
+    } == crypto_aead_xchacha20poly1305_ietf_NPUBBYTES)
+}
+#[test]
+fn test_crypto_aead_xchacha20poly1305_ietf_abytes() {
+    assert!(unsafe {
+        crypto_aead_xchacha20poly1305_ietf_abytes() as usize
+    } == crypto_aead_xchacha20poly1305_ietf_ABYTES)
+}
+#[test]
+fn test_crypto_aead_aes256gcm_keybytes() {
+    assert!(unsafe { crypto_aead_aes256gcm_keybytes() as usize } ==
+            crypto_aead_aes256gcm_KEYBYTES)
+}
+#[test]
+fn test_crypto_aead_aes256gcm_nsecbytes() {
+    assert!(unsafe { crypto_aead_aes256gcm_nsecbytes() as usize } ==
+            crypto_aead_aes256gcm_NSECBYTES)
+}
+#[test]
+fn test_crypto_aead_aes256gcm_npubbytes() {
+    assert!(unsafe { crypto_aead_aes256gcm_npubbytes() as usize } ==
+            crypto_aead_aes256gcm_NPUBBYTES)
+}
+#[test]
+fn test_crypto_aead_aes256gcm_abytes() {
+    assert!(unsafe { crypto_aead_aes256gcm_abytes() as usize } ==
+            crypto_aead_aes256gcm_ABYTES)
+}
+#[test]
+fn test_crypto_aead_aes256gcm_statebytes() {
+    assert!(unsafe { crypto_aead_aes256gcm_statebytes() as usize } ==
+            crypto_aead_aes256gcm_STATEBYTES)
+}
+#[test]
+fn test_crypto_aead_aes256gcm_state_alignment() {
+    assert!(std::mem::align_of::<crypto_aead_aes256gcm_state>() == 16);
+    assert!(std::mem::size_of::<crypto_aead_aes256gcm_state>() == crypto_aead_aes256gcm_STATEBYTES);
+}
 
 // auth
 // crypto_auth.h
@@ -572,6 +1440,11 @@ fn test_crypto_auth_hmacsha256_keybytes() {
     assert!(unsafe { crypto_auth_hmacsha256_keybytes() as usize } ==
             crypto_auth_hmacsha256_KEYBYTES)
 }
+#[test]
+fn test_crypto_auth_hmacsha256_statebytes() {
+    assert!(unsafe { crypto_auth_hmacsha256_statebytes() as usize } ==
+            std::mem::size_of::<crypto_auth_hmacsha256_state>())
+}
 
 #[test]
 fn test_crypto_auth_hmacsha512_bytes() {
@@ -583,6 +1456,11 @@ fn test_crypto_auth_hmacsha512_keybytes() {
     assert!(unsafe { crypto_auth_hmacsha512_keybytes() as usize } ==
             crypto_auth_hmacsha512_KEYBYTES)
 }
+#[test]
+fn test_crypto_auth_hmacsha512_statebytes() {
+    assert!(unsafe { crypto_auth_hmacsha512_statebytes() as usize } ==
+            std::mem::size_of::<crypto_auth_hmacsha512_state>())
+}
 
 #[test]
 fn test_crypto_auth_hmacsha512256_bytes() {
@@ -594,6 +1472,11 @@ fn test_crypto_auth_hmacsha512256_keybytes() {
     assert!(unsafe { crypto_auth_hmacsha512256_keybytes() as usize } ==
             crypto_auth_hmacsha512256_KEYBYTES)
 }
+#[test]
+fn test_crypto_auth_hmacsha512256_statebytes() {
+    assert!(unsafe { crypto_auth_hmacsha512256_statebytes() as usize } ==
+            std::mem::size_of::<crypto_auth_hmacsha512256_state>())
+}
 
 // onetimeauth
 #[test]
@@ -624,6 +1507,17 @@ fn test_crypto_onetimeauth_poly1305_keybytes() {
     assert!(unsafe { crypto_onetimeauth_poly1305_keybytes() as usize } ==
             crypto_onetimeauth_poly1305_KEYBYTES)
 }
+#[test]
+fn test_crypto_onetimeauth_poly1305_statebytes() {
+    assert!(unsafe { crypto_onetimeauth_poly1305_statebytes() as usize } ==
+            crypto_onetimeauth_poly1305_STATEBYTES)
+}
+#[test]
+fn test_crypto_onetimeauth_poly1305_state_size() {
+    assert!(std::mem::align_of::<crypto_onetimeauth_poly1305_state>() == 16);
+    assert!(std::mem::size_of::<crypto_onetimeauth_poly1305_state>() ==
+            crypto_onetimeauth_poly1305_STATEBYTES)
+}
 
 //pwhash
 #[test]
@@ -696,6 +1590,166 @@ fn test_crypto_pwhash_scryptsalsa208sha256_str() {
     assert!(ret_verify == 0);
 }
 #[test]
+fn test_crypto_pwhash_argon2i_alg_argon2i13() {
+    assert!(unsafe {
+        crypto_pwhash_argon2i_alg_argon2i13()
+    } == crypto_pwhash_argon2i_ALG_ARGON2I13)
+}
+#[test]
+fn test_crypto_pwhash_argon2i_saltbytes() {
+    assert!(unsafe {
+        crypto_pwhash_argon2i_saltbytes() as usize
+    } == crypto_pwhash_argon2i_SALTBYTES)
+}
+#[test]
+fn test_crypto_pwhash_argon2i_strbytes() {
+    assert!(unsafe {
+        crypto_pwhash_argon2i_strbytes() as usize
+    } == crypto_pwhash_argon2i_STRBYTES)
+}
+#[test]
+fn test_crypto_pwhash_argon2i_bytes_min() {
+    assert!(unsafe {
+        crypto_pwhash_argon2i_bytes_min() as usize
+    } == crypto_pwhash_argon2i_BYTES_MIN)
+}
+#[test]
+fn test_crypto_pwhash_argon2i_opslimit_min() {
+    assert!(unsafe {
+        crypto_pwhash_argon2i_opslimit_min() as usize
+    } == crypto_pwhash_argon2i_OPSLIMIT_MIN)
+}
+#[test]
+fn test_crypto_pwhash_argon2i_memlimit_min() {
+    assert!(unsafe {
+        crypto_pwhash_argon2i_memlimit_min() as usize
+    } == crypto_pwhash_argon2i_MEMLIMIT_MIN)
+}
+#[test]
+fn test_crypto_pwhash_argon2i_opslimit_interactive() {
+    assert!(unsafe {
+        crypto_pwhash_argon2i_opslimit_interactive() as usize
+    } == crypto_pwhash_argon2i_OPSLIMIT_INTERACTIVE)
+}
+#[test]
+fn test_crypto_pwhash_argon2i_memlimit_interactive() {
+    assert!(unsafe {
+        crypto_pwhash_argon2i_memlimit_interactive() as usize
+    } == crypto_pwhash_argon2i_MEMLIMIT_INTERACTIVE)
+}
+#[test]
+fn test_crypto_pwhash_argon2i_opslimit_moderate() {
+    assert!(unsafe {
+        crypto_pwhash_argon2i_opslimit_moderate() as usize
+    } == crypto_pwhash_argon2i_OPSLIMIT_MODERATE)
+}
+#[test]
+fn test_crypto_pwhash_argon2i_memlimit_moderate() {
+    assert!(unsafe {
+        crypto_pwhash_argon2i_memlimit_moderate() as usize
+    } == crypto_pwhash_argon2i_MEMLIMIT_MODERATE)
+}
+#[test]
+fn test_crypto_pwhash_argon2i_opslimit_sensitive() {
+    assert!(unsafe {
+        crypto_pwhash_argon2i_opslimit_sensitive() as usize
+    } == crypto_pwhash_argon2i_OPSLIMIT_SENSITIVE)
+}
+#[test]
+fn test_crypto_pwhash_argon2i_memlimit_sensitive() {
+    assert!(unsafe {
+        crypto_pwhash_argon2i_memlimit_sensitive() as usize
+    } == crypto_pwhash_argon2i_MEMLIMIT_SENSITIVE)
+}
+#[test]
+fn test_crypto_pwhash_argon2i_strprefix() {
+    unsafe {
+        let s = crypto_pwhash_argon2i_strprefix();
+        let s = std::ffi::c_str_to_bytes(&s);
+        assert!(s == crypto_pwhash_argon2i_STRPREFIX.as_bytes());
+    }
+}
+#[test]
+fn test_crypto_pwhash_argon2id_alg_argon2id13() {
+    assert!(unsafe {
+        crypto_pwhash_argon2id_alg_argon2id13()
+    } == crypto_pwhash_argon2id_ALG_ARGON2ID13)
+}
+#[test]
+fn test_crypto_pwhash_argon2id_saltbytes() {
+    assert!(unsafe {
+        crypto_pwhash_argon2id_saltbytes() as usize
+    } == crypto_pwhash_argon2id_SALTBYTES)
+}
+#[test]
+fn test_crypto_pwhash_argon2id_strbytes() {
+    assert!(unsafe {
+        crypto_pwhash_argon2id_strbytes() as usize
+    } == crypto_pwhash_argon2id_STRBYTES)
+}
+#[test]
+fn test_crypto_pwhash_argon2id_bytes_min() {
+    assert!(unsafe {
+        crypto_pwhash_argon2id_bytes_min() as usize
+    } == crypto_pwhash_argon2id_BYTES_MIN)
+}
+#[test]
+fn test_crypto_pwhash_argon2id_opslimit_min() {
+    assert!(unsafe {
+        crypto_pwhash_argon2id_opslimit_min() as usize
+    } == crypto_pwhash_argon2id_OPSLIMIT_MIN)
+}
+#[test]
+fn test_crypto_pwhash_argon2id_memlimit_min() {
+    assert!(unsafe {
+        crypto_pwhash_argon2id_memlimit_min() as usize
+    } == crypto_pwhash_argon2id_MEMLIMIT_MIN)
+}
+#[test]
+fn test_crypto_pwhash_argon2id_opslimit_interactive() {
+    assert!(unsafe {
+        crypto_pwhash_argon2id_opslimit_interactive() as usize
+    } == crypto_pwhash_argon2id_OPSLIMIT_INTERACTIVE)
+}
+#[test]
+fn test_crypto_pwhash_argon2id_memlimit_interactive() {
+    assert!(unsafe {
+        crypto_pwhash_argon2id_memlimit_interactive() as usize
+    } == crypto_pwhash_argon2id_MEMLIMIT_INTERACTIVE)
+}
+#[test]
+fn test_crypto_pwhash_argon2id_opslimit_moderate() {
+    assert!(unsafe {
+        crypto_pwhash_argon2id_opslimit_moderate() as usize
+    } == crypto_pwhash_argon2id_OPSLIMIT_MODERATE)
+}
+#[test]
+fn test_crypto_pwhash_argon2id_memlimit_moderate() {
+    assert!(unsafe {
+        crypto_pwhash_argon2id_memlimit_moderate() as usize
+    } == crypto_pwhash_argon2id_MEMLIMIT_MODERATE)
+}
+#[test]
+fn test_crypto_pwhash_argon2id_opslimit_sensitive() {
+    assert!(unsafe {
+        crypto_pwhash_argon2id_opslimit_sensitive() as usize
+    } == crypto_pwhash_argon2id_OPSLIMIT_SENSITIVE)
+}
+#[test]
+fn test_crypto_pwhash_argon2id_memlimit_sensitive() {
+    assert!(unsafe {
+        crypto_pwhash_argon2id_memlimit_sensitive() as usize
+    } == crypto_pwhash_argon2id_MEMLIMIT_SENSITIVE)
+}
+#[test]
+fn test_crypto_pwhash_argon2id_strprefix() {
+    unsafe {
+        let s = crypto_pwhash_argon2id_strprefix();
+        let s = std::ffi::c_str_to_bytes(&s);
+        assert!(s == crypto_pwhash_argon2id_STRPREFIX.as_bytes());
+    }
+}
+#[test]
 fn test_crypto_pwhash_scryptsalsa208sha256_ll_1() {
     // See https://www.tarsnap.com/scrypt/scrypt.pdf Page 16
     let password = "";
@@ -839,12 +1893,82 @@ fn test_crypto_hash_sha256_bytes() {
     assert!(unsafe { crypto_hash_sha256_bytes() as usize } ==
             crypto_hash_sha256_BYTES)
 }
+#[test]
+fn test_crypto_hash_sha256_statebytes() {
+    assert!(unsafe { crypto_hash_sha256_statebytes() as usize } ==
+            std::mem::size_of::<crypto_hash_sha256_state>())
+}
 
 #[test]
 fn test_crypto_hash_sha512_bytes() {
     assert!(unsafe { crypto_hash_sha512_bytes() as usize } ==
             crypto_hash_sha512_BYTES)
 }
+#[test]
+fn test_crypto_hash_sha512_statebytes() {
+    assert!(unsafe { crypto_hash_sha512_statebytes() as usize } ==
+            std::mem::size_of::<crypto_hash_sha512_state>())
+}
+
+// generichash
+#[test]
+fn test_crypto_generichash_blake2b_bytes_min() {
+    assert!(unsafe {
+        crypto_generichash_blake2b_bytes_min() as usize
+    } == crypto_generichash_blake2b_BYTES_MIN)
+}
+#[test]
+fn test_crypto_generichash_blake2b_bytes_max() {
+    assert!(unsafe {
+        crypto_generichash_blake2b_bytes_max() as usize
+    } == crypto_generichash_blake2b_BYTES_MAX)
+}
+#[test]
+fn test_crypto_generichash_blake2b_bytes() {
+    assert!(unsafe {
+        crypto_generichash_blake2b_bytes() as usize
+    } == crypto_generichash_blake2b_BYTES)
+}
+#[test]
+fn test_crypto_generichash_blake2b_keybytes_min() {
+    assert!(unsafe {
+        crypto_generichash_blake2b_keybytes_min() as usize
+    } == crypto_generichash_blake2b_KEYBYTES_MIN)
+}
+#[test]
+fn test_crypto_generichash_blake2b_keybytes_max() {
+    assert!(unsafe {
+        crypto_generichash_blake2b_keybytes_max() as usize
+    } == crypto_generichash_blake2b_KEYBYTES_MAX)
+}
+#[test]
+fn test_crypto_generichash_blake2b_keybytes() {
+    assert!(unsafe {
+        crypto_generichash_blake2b_keybytes() as usize
+    } == crypto_generichash_blake2b_KEYBYTES)
+}
+#[test]
+fn test_crypto_generichash_blake2b_saltbytes() {
+    assert!(unsafe {
+        crypto_generichash_blake2b_saltbytes() as usize
+    } == crypto_generichash_blake2b_SALTBYTES)
+}
+#[test]
+fn test_crypto_generichash_blake2b_personalbytes() {
+    assert!(unsafe {
+        crypto_generichash_blake2b_personalbytes() as usize
+    } == crypto_generichash_blake2b_PERSONALBYTES)
+}
+#[test]
+fn test_crypto_generichash_blake2b_statebytes() {
+    assert!(unsafe {
+        crypto_generichash_blake2b_statebytes() as usize
+    } == crypto_generichash_blake2b_STATEBYTES)
+}
+#[test]
+fn test_crypto_generichash_blake2b_state_size() {
+    assert!(std::mem::size_of::<crypto_generichash_blake2b_state>() == crypto_generichash_blake2b_STATEBYTES)
+}
 
 // stream
 #[test]
@@ -987,6 +2111,52 @@ fn test_crypto_box_curve25519xsalsa20poly1305_macbytes() {
     } == crypto_box_curve25519xsalsa20poly1305_MACBYTES)
 }
 
+// crypto_box_curve25519xchacha20poly1305.h
+#[test]
+fn test_crypto_box_curve25519xchacha20poly1305_seedbytes() {
+    assert!(unsafe {
+        crypto_box_curve25519xchacha20poly1305_seedbytes() as usize
+    } == crypto_box_curve25519xchacha20poly1305_SEEDBYTES)
+}
+#[test]
+fn test_crypto_box_curve25519xchacha20poly1305_publickeybytes() {
+    assert!(unsafe {
+        crypto_box_curve25519xchacha20poly1305_publickeybytes() as usize
+    } == crypto_box_curve25519xchacha20poly1305_PUBLICKEYBYTES)
+}
+#[test]
+fn test_crypto_box_curve25519xchacha20poly1305_secretkeybytes() {
+    assert!(unsafe {
+        crypto_box_curve25519xchacha20poly1305_secretkeybytes() as usize
+    } == crypto_box_curve25519xchacha20poly1305_SECRETKEYBYTES)
+}
+#[test]
+fn test_crypto_box_curve25519xchacha20poly1305_beforenmbytes() {
+    assert!(unsafe {
+        crypto_box_curve25519xchacha20poly1305_beforenmbytes() as usize
+    } == crypto_box_curve25519xchacha20poly1305_BEFORENMBYTES)
+}
+#[test]
+fn test_crypto_box_curve25519xchacha20poly1305_noncebytes() {
+    assert!(unsafe {
+        crypto_box_curve25519xchacha20poly1305_noncebytes() as usize
+    } == crypto_box_curve25519xchacha20poly1305_NONCEBYTES)
+}
+#[test]
+fn test_crypto_box_curve25519xchacha20poly1305_macbytes() {
+    assert!(unsafe {
+        crypto_box_curve25519xchacha20poly1305_macbytes() as usize
+    } == crypto_box_curve25519xchacha20poly1305_MACBYTES)
+}
+
+// sealed box
+#[test]
+fn test_crypto_box_sealbytes() {
+    assert!(unsafe {
+        crypto_box_sealbytes() as usize
+    } == crypto_box_SEALBYTES)
+}
+
 // scalarmult
 #[test]
 fn test_crypto_scalarmult_curve25519_bytes() {
@@ -1091,3 +2261,57 @@ fn test_crypto_secretbox_xsalsa20poly1305_macbytes() {
         crypto_secretbox_xsalsa20poly1305_macbytes() as usize
     } == crypto_secretbox_xsalsa20poly1305_MACBYTES)
 }
+#[test]
+fn test_crypto_secretbox_xchacha20poly1305_keybytes() {
+    assert!(unsafe {
+        crypto_secretbox_xchacha20poly1305_keybytes() as usize
+    } == crypto_secretbox_xchacha20poly1305_KEYBYTES)
+}
+#[test]
+fn test_crypto_secretbox_xchacha20poly1305_noncebytes() {
+    assert!(unsafe {
+        crypto_secretbox_xchacha20poly1305_noncebytes() as usize
+    } == crypto_secretbox_xchacha20poly1305_NONCEBYTES)
+}
+#[test]
+fn test_crypto_secretbox_xchacha20poly1305_macbytes() {
+    assert!(unsafe {
+        crypto_secretbox_xchacha20poly1305_macbytes() as usize
+    } == crypto_secretbox_xchacha20poly1305_MACBYTES)
+}
+
+// secretstream
+#[test]
+fn test_crypto_secretstream_xchacha20poly1305_abytes() {
+    assert!(unsafe {
+        crypto_secretstream_xchacha20poly1305_abytes() as usize
+    } == crypto_secretstream_xchacha20poly1305_ABYTES)
+}
+#[test]
+fn test_crypto_secretstream_xchacha20poly1305_headerbytes() {
+    assert!(unsafe {
+        crypto_secretstream_xchacha20poly1305_headerbytes() as usize
+    } == crypto_secretstream_xchacha20poly1305_HEADERBYTES)
+}
+#[test]
+fn test_crypto_secretstream_xchacha20poly1305_keybytes() {
+    assert!(unsafe {
+        crypto_secretstream_xchacha20poly1305_keybytes() as usize
+    } == crypto_secretstream_xchacha20poly1305_KEYBYTES)
+}
+#[test]
+fn test_crypto_secretstream_xchacha20poly1305_statebytes() {
+    assert!(unsafe {
+        crypto_secretstream_xchacha20poly1305_statebytes() as usize
+    } == crypto_secretstream_xchacha20poly1305_STATEBYTES);
+    assert!(std::mem::size_of::<crypto_secretstream_xchacha20poly1305_state>() == crypto_secretstream_xchacha20poly1305_STATEBYTES)
+}
+#[test]
+fn test_crypto_secretstream_xchacha20poly1305_tags() {
+    unsafe {
+        assert!(crypto_secretstream_xchacha20poly1305_tag_message() == crypto_secretstream_xchacha20poly1305_TAG_MESSAGE);
+        assert!(crypto_secretstream_xchacha20poly1305_tag_push() == crypto_secretstream_xchacha20poly1305_TAG_PUSH);
+        assert!(crypto_secretstream_xchacha20poly1305_tag_rekey() == crypto_secretstream_xchacha20poly1305_TAG_REKEY);
+        assert!(crypto_secretstream_xchacha20poly1305_tag_final() == crypto_secretstream_xchacha20poly1305_TAG_FINAL);
+    }
+}
@@ -0,0 +1,72 @@
+/*!
+Authenticated encryption with associated data (AEAD)
+
+# Security model
+The `seal()` function is designed to meet the standard notions of privacy and
+authenticity for a secret-key authenticated-encryption scheme using nonces.
+In addition to the message, `seal()` authenticates optional associated data
+`ad` which is not encrypted and not included in the ciphertext. This is useful
+for protocol headers that have to be readable by intermediaries but must not
+be modified.
+
+`open()` only returns the plaintext if both the ciphertext and the associated
+data are authentic.
+
+Note that the length is not hidden. Note also that it is the caller's
+responsibility to ensure the uniqueness of nonces—for example, by using
+nonce 1 for the first message, nonce 2 for the second message, etc.
+
+# Selected primitive
+`seal()` is `crypto_aead_xchacha20poly1305_ietf`, the IETF ChaCha20-Poly1305
+construction extended to 24-byte nonces, as specified in
+[XChaCha: eXtended-nonce ChaCha and AEAD_XChaCha20_Poly1305](https://tools.ietf.org/html/draft-irtf-cfrg-xchacha-03).
+Nonces are long enough that randomly generated nonces have negligible risk of
+collision, so `gen_nonce()` can safely be used for every message.
+
+# Alternate primitives
+----------------------------------------------------------------------
+|crypto_aead                       |KEYBYTES |NONCEBYTES |TAGBYTES |
+|----------------------------------|---------|-----------|---------|
+|crypto_aead_chacha20poly1305      |32       |8          |16       |
+|crypto_aead_chacha20poly1305_ietf |32       |12         |16       |
+|crypto_aead_xchacha20poly1305_ietf|32       |24         |16       |
+|crypto_aead_aes256gcm             |32       |12         |16       |
+----------------------------------------------------------------------
+
+`crypto_aead_chacha20poly1305` is the original construction combining the
+ChaCha20 stream cipher with the Poly1305 authenticator, as described in
+[ChaCha20 and Poly1305 based Cipher Suites for TLS](https://tools.ietf.org/html/draft-agl-tls-chacha20poly1305-04).
+`crypto_aead_chacha20poly1305_ietf` is the variant specified in
+[RFC 8439](https://tools.ietf.org/html/rfc8439) and should be used when
+interoperating with TLS, QUIC or WireGuard style peers.
+
+Beware that `crypto_aead_chacha20poly1305` has 8-byte nonces and that
+`crypto_aead_chacha20poly1305_ietf` has 12-byte nonces. For these
+primitives it is not true that randomly generated nonces have negligible risk
+of collision. Callers who are unable to count 1, 2, 3..., and who insist on
+using these primitives, are advised to use a randomly derived key for each
+message.
+
+`crypto_aead_aes256gcm` is AES-256-GCM, for interoperability with
+protocols that mandate it. It requires hardware support; check
+`aes256gcm::is_available()` before using it. It has 12-byte nonces, so the
+warning above applies to it as well. If many messages are encrypted with
+the same key, `aes256gcm::precompute()` expands the key once for use with
+`seal_precomputed()` and `open_precomputed()`.
+
+All primitives also support a detached mode (`seal_detached()` and
+`open_detached()`) which encrypts in place and returns the authentication tag
+separately.
+*/
+pub use self::xchacha20poly1305_ietf::*;
+#[path="aead_macros.rs"]
+#[macro_use]
+mod aead_macros;
+#[path="chacha20poly1305.rs"]
+pub mod chacha20poly1305;
+#[path="chacha20poly1305_ietf.rs"]
+pub mod chacha20poly1305_ietf;
+#[path="xchacha20poly1305_ietf.rs"]
+pub mod xchacha20poly1305_ietf;
+#[path="aes256gcm.rs"]
+pub mod aes256gcm;
@@ -0,0 +1,405 @@
+macro_rules! aead_module (($seal_name:ident,
+                           $open_name:ident,
+                           $seal_detached_name:ident,
+                           $open_detached_name:ident,
+                           $keybytes:expr,
+                           $noncebytes:expr,
+                           $tagbytes:expr,
+                           $available:expr) => (
+
+use libc::c_ulonglong;
+use std::intrinsics::volatile_set_memory;
+use std::iter::repeat;
+use std::ops::{Index, Range, RangeFrom, RangeFull, RangeTo};
+use std::ptr;
+use randombytes::randombytes_into;
+use crypto::verify::verify_16;
+
+pub const KEYBYTES: usize = $keybytes;
+pub const NONCEBYTES: usize = $noncebytes;
+pub const TAGBYTES: usize = $tagbytes;
+
+/**
+ * `Key` for authenticated encryption with associated data
+ *
+ * When a `Key` goes out of scope its contents
+ * will be zeroed out
+ */
+pub struct Key(pub [u8; KEYBYTES]);
+
+newtype_drop!(Key);
+newtype_clone!(Key);
+newtype_impl!(Key, KEYBYTES);
+
+/**
+ * `Nonce` for authenticated encryption with associated data
+ */
+#[derive(Copy)]
+pub struct Nonce(pub [u8; NONCEBYTES]);
+
+newtype_clone!(Nonce);
+newtype_impl!(Nonce, NONCEBYTES);
+
+/**
+ * Authentication `Tag` for the detached mode
+ *
+ * The tag implements the traits `PartialEq` and `Eq` using constant-time
+ * comparison functions. See `sodiumoxide::crypto::verify::verify_16`
+ */
+#[derive(Copy)]
+pub struct Tag(pub [u8; TAGBYTES]);
+
+impl Eq for Tag {}
+
+impl PartialEq for Tag {
+    fn eq(&self, &Tag(other): &Tag) -> bool {
+        let &Tag(ref tag) = self;
+        verify_16(tag, &other)
+    }
+}
+
+newtype_clone!(Tag);
+newtype_impl!(Tag, TAGBYTES);
+
+/**
+ * `gen_key()` randomly generates a secret key
+ *
+ * THREAD SAFETY: `gen_key()` is thread-safe provided that you have
+ * called `sodiumoxide::init()` once before using any other function
+ * from sodiumoxide.
+ */
+pub fn gen_key() -> Key {
+    let mut key = [0; KEYBYTES];
+    randombytes_into(&mut key);
+    Key(key)
+}
+
+/**
+ * `gen_nonce()` randomly generates a nonce
+ *
+ * THREAD SAFETY: `gen_nonce()` is thread-safe provided that you have
+ * called `sodiumoxide::init()` once before using any other function
+ * from sodiumoxide.
+ *
+ * NOTE: When using primitives with short nonces (e.g. chacha20poly1305,
+ * chacha20poly1305_ietf, aes256gcm)
+ * do not use random nonces since the probability of nonce-collision is not negligible
+ */
+pub fn gen_nonce() -> Nonce {
+    let mut nonce = [0; NONCEBYTES];
+    randombytes_into(&mut nonce);
+    Nonce(nonce)
+}
+
+fn ad_ptr_len(ad: Option<&[u8]>) -> (*const u8, c_ulonglong) {
+    match ad {
+        Some(ad) => (ad.as_ptr(), ad.len() as c_ulonglong),
+        None => (ptr::null(), 0)
+    }
+}
+
+/**
+ * `seal()` encrypts and authenticates a message `m` together with optional
+ * associated data `ad` using a secret key `k` and a nonce `n`.
+ * It returns a ciphertext `c`.
+ *
+ * The associated data is authenticated but not encrypted, and is not
+ * included in `c`.
+ */
+pub fn seal(m: &[u8],
+            ad: Option<&[u8]>,
+            &Nonce(ref n): &Nonce,
+            &Key(ref k): &Key) -> Vec<u8> {
+    let (ad_p, ad_len) = ad_ptr_len(ad);
+    let mut c: Vec<u8> = repeat(0u8).take(m.len() + TAGBYTES).collect();
+    let mut clen = 0;
+    unsafe {
+        $seal_name(c.as_mut_ptr(),
+                   &mut clen,
+                   m.as_ptr(),
+                   m.len() as c_ulonglong,
+                   ad_p,
+                   ad_len,
+                   ptr::null(),
+                   n,
+                   k);
+    }
+    c.truncate(clen as usize);
+    c
+}
+
+/**
+ * `open()` verifies and decrypts a ciphertext `c` together with optional
+ * associated data `ad` using a secret key `k` and a nonce `n`.
+ * It returns a plaintext `Some(m)`.
+ * If the ciphertext or the associated data fails verification, `open()` returns `None`.
+ */
+pub fn open(c: &[u8],
+            ad: Option<&[u8]>,
+            &Nonce(ref n): &Nonce,
+            &Key(ref k): &Key) -> Option<Vec<u8>> {
+    if c.len() < TAGBYTES {
+        return None
+    }
+    let (ad_p, ad_len) = ad_ptr_len(ad);
+    let mut m: Vec<u8> = repeat(0u8).take(c.len() - TAGBYTES).collect();
+    let mut mlen = 0;
+    let ret = unsafe {
+        $open_name(m.as_mut_ptr(),
+                   &mut mlen,
+                   ptr::null_mut(),
+                   c.as_ptr(),
+                   c.len() as c_ulonglong,
+                   ad_p,
+                   ad_len,
+                   n,
+                   k)
+    };
+    if ret == 0 {
+        m.truncate(mlen as usize);
+        Some(m)
+    } else {
+        None
+    }
+}
+
+/**
+ * `seal_detached()` encrypts and authenticates a message `m` together with
+ * optional associated data `ad` using a secret key `k` and a nonce `n`.
+ * `m` is encrypted in place, so after `seal_detached()` returns it will
+ * contain the ciphertext. The authentication tag is returned separately.
+ */
+pub fn seal_detached(m: &mut [u8],
+                     ad: Option<&[u8]>,
+                     &Nonce(ref n): &Nonce,
+                     &Key(ref k): &Key) -> Tag {
+    let (ad_p, ad_len) = ad_ptr_len(ad);
+    let mut tag = [0u8; TAGBYTES];
+    unsafe {
+        $seal_detached_name(m.as_mut_ptr(),
+                            &mut tag,
+                            ptr::null_mut(),
+                            m.as_ptr(),
+                            m.len() as c_ulonglong,
+                            ad_p,
+                            ad_len,
+                            ptr::null(),
+                            n,
+                            k);
+    }
+    Tag(tag)
+}
+
+/**
+ * `open_detached()` verifies and decrypts a ciphertext `c` together with
+ * optional associated data `ad` using a secret key `k`, a nonce `n` and the
+ * authentication tag `tag`. `c` is decrypted in place, so if `open_detached()`
+ * returns `Ok(())` it will contain the plaintext.
+ *
+ * If the ciphertext or the associated data fails verification,
+ * `open_detached()` returns `Err(())` and the contents of `c` are
+ * overwritten with zeros.
+ */
+pub fn open_detached(c: &mut [u8],
+                     ad: Option<&[u8]>,
+                     &Tag(ref tag): &Tag,
+                     &Nonce(ref n): &Nonce,
+                     &Key(ref k): &Key) -> Result<(), ()> {
+    let (ad_p, ad_len) = ad_ptr_len(ad);
+    let ret = unsafe {
+        $open_detached_name(c.as_mut_ptr(),
+                            ptr::null_mut(),
+                            c.as_ptr(),
+                            c.len() as c_ulonglong,
+                            tag,
+                            ad_p,
+                            ad_len,
+                            n,
+                            k)
+    };
+    if ret == 0 {
+        Ok(())
+    } else {
+        Err(())
+    }
+}
+
+#[test]
+fn test_seal_open() {
+    if !$available {
+        return
+    }
+    use randombytes::randombytes;
+    for i in (0..256us) {
+        let k = gen_key();
+        let n = gen_nonce();
+        let ad = randombytes(i);
+        let m = randombytes(i);
+        let c = seal(&m, Some(&ad[..]), &n, &k);
+        let opened = open(&c, Some(&ad[..]), &n, &k);
+        assert!(Some(m) == opened);
+    }
+}
+
+#[test]
+fn test_seal_open_no_ad() {
+    if !$available {
+        return
+    }
+    use randombytes::randombytes;
+    for i in (0..256us) {
+        let k = gen_key();
+        let n = gen_nonce();
+        let m = randombytes(i);
+        let empty: &[u8] = &[];
+        let c = seal(&m, None, &n, &k);
+        assert!(Some(m.clone()) == open(&c, None, &n, &k));
+        assert!(Some(m) == open(&c, Some(empty), &n, &k));
+    }
+}
+
+#[test]
+fn test_seal_open_tamper() {
+    if !$available {
+        return
+    }
+    use randombytes::randombytes;
+    for i in (0..32us) {
+        let k = gen_key();
+        let n = gen_nonce();
+        let mut adv = randombytes(i);
+        let m = randombytes(i);
+        let mut cv = seal(&m, Some(&adv[..]), &n, &k);
+        let ad = adv.as_mut_slice();
+        let c = cv.as_mut_slice();
+        for j in (0..c.len()) {
+            c[j] ^= 0x20;
+            assert!(None == open(c, Some(&ad[..]), &n, &k));
+            c[j] ^= 0x20;
+        }
+        for j in (0..ad.len()) {
+            ad[j] ^= 0x20;
+            assert!(None == open(c, Some(&ad[..]), &n, &k));
+            ad[j] ^= 0x20;
+        }
+    }
+}
+
+#[test]
+fn test_seal_open_detached() {
+    if !$available {
+        return
+    }
+    use randombytes::randombytes;
+    for i in (0..256us) {
+        let k = gen_key();
+        let n = gen_nonce();
+        let ad = randombytes(i);
+        let m = randombytes(i);
+        let mut buf = m.clone();
+        let tag = seal_detached(buf.as_mut_slice(), Some(&ad[..]), &n, &k);
+        assert!(open_detached(buf.as_mut_slice(), Some(&ad[..]), &tag, &n, &k).is_ok());
+        assert!(m == buf);
+    }
+}
+
+#[test]
+fn test_seal_detached_same() {
+    if !$available {
+        return
+    }
+    use randombytes::randombytes;
+    for i in (0..256us) {
+        let k = gen_key();
+        let n = gen_nonce();
+        let ad = randombytes(i);
+        let m = randombytes(i);
+        let c = seal(&m, Some(&ad[..]), &n, &k);
+        let mut buf = m.clone();
+        let Tag(tag) = seal_detached(buf.as_mut_slice(), Some(&ad[..]), &n, &k);
+        assert!(&c[..m.len()] == &buf[..]);
+        assert!(&c[m.len()..] == &tag[..]);
+    }
+}
+
+#[test]
+fn test_seal_open_detached_tamper() {
+    if !$available {
+        return
+    }
+    use randombytes::randombytes;
+    for i in (0..32us) {
+        let k = gen_key();
+        let n = gen_nonce();
+        let mut ad = randombytes(i);
+        let mut c = randombytes(i);
+        let Tag(mut tagbuf) = seal_detached(c.as_mut_slice(), Some(&ad[..]), &n, &k);
+        for j in (0..c.len()) {
+            let mut tampered = c.clone();
+            tampered[j] ^= 0x20;
+            assert!(open_detached(tampered.as_mut_slice(), Some(&ad[..]),
+                                  &Tag(tagbuf), &n, &k).is_err());
+        }
+        for j in (0..ad.len()) {
+            let mut cv = c.clone();
+            ad[j] ^= 0x20;
+            assert!(open_detached(cv.as_mut_slice(), Some(&ad[..]),
+                                  &Tag(tagbuf), &n, &k).is_err());
+            ad[j] ^= 0x20;
+        }
+        for j in (0..tagbuf.len()) {
+            let mut cv = c.clone();
+            tagbuf[j] ^= 0x20;
+            assert!(open_detached(cv.as_mut_slice(), Some(&ad[..]),
+                                  &Tag(tagbuf), &n, &k).is_err());
+            tagbuf[j] ^= 0x20;
+        }
+    }
+}
+
+#[cfg(test)]
+mod bench {
+    extern crate test;
+    use randombytes::randombytes;
+    use super::*;
+
+    const BENCH_SIZES: [usize; 14] = [0, 1, 2, 4, 8, 16, 32, 64,
+                                      128, 256, 512, 1024, 2048, 4096];
+
+    #[bench]
+    fn bench_seal_open(b: &mut test::Bencher) {
+        if !$available {
+            return
+        }
+        let k = gen_key();
+        let n = gen_nonce();
+        let ms: Vec<Vec<u8>> = BENCH_SIZES.iter().map(|s| {
+            randombytes(*s)
+        }).collect();
+        b.iter(|| {
+            for m in ms.iter() {
+                open(&seal(&m, None, &n, &k), None, &n, &k).unwrap();
+            }
+        });
+    }
+
+    #[bench]
+    fn bench_seal_open_detached(b: &mut test::Bencher) {
+        if !$available {
+            return
+        }
+        let k = gen_key();
+        let n = gen_nonce();
+        let mut ms: Vec<Vec<u8>> = BENCH_SIZES.iter().map(|s| {
+            randombytes(*s)
+        }).collect();
+        b.iter(|| {
+            for m in ms.iter_mut() {
+                let tag = seal_detached(m.as_mut_slice(), None, &n, &k);
+                open_detached(m.as_mut_slice(), None, &tag, &n, &k).unwrap();
+            }
+        });
+    }
+}
+
+));
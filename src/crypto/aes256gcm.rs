@@ -0,0 +1,315 @@
+/*!
+`crypto_aead_aes256gcm`, AES-256 in Galois/Counter Mode as specified in
+[NIST SP 800-38D](http://csrc.nist.gov/publications/nistpubs/800-38D/SP-800-38D.pdf).
+
+This implementation is only available on CPUs that support the AES-NI and
+CLMUL instructions. Use `is_available()` to check for support at runtime
+before calling any other function from this module. `seal()`, `open()` and
+the other functions of this module panic if the CPU lacks support.
+
+Nonces are 12 bytes long, so randomly generated nonces have a non-negligible
+risk of collision. Use a counter instead.
+*/
+use ffi;
+use ffi::{crypto_aead_aes256gcm_state,
+          crypto_aead_aes256gcm_KEYBYTES,
+          crypto_aead_aes256gcm_NSECBYTES,
+          crypto_aead_aes256gcm_NPUBBYTES,
+          crypto_aead_aes256gcm_ABYTES};
+use libc::c_int;
+use std::sync::{Once, ONCE_INIT};
+
+aead_module!(encrypt,
+             decrypt,
+             encrypt_detached,
+             decrypt_detached,
+             crypto_aead_aes256gcm_KEYBYTES,
+             crypto_aead_aes256gcm_NPUBBYTES,
+             crypto_aead_aes256gcm_ABYTES,
+             is_available());
+
+static DETECT_CPU_FEATURES: Once = ONCE_INIT;
+
+/**
+ * `is_available()` returns `true` if the CPU supports the instructions
+ * required by AES-256-GCM.
+ *
+ * The CPU features are detected by `sodiumoxide::init()`. If that has not
+ * happened yet, the first call to `is_available()` initializes the sodium
+ * library.
+ */
+pub fn is_available() -> bool {
+    DETECT_CPU_FEATURES.call_once(|| unsafe {
+        ffi::sodium_init();
+    });
+    unsafe {
+        ffi::crypto_aead_aes256gcm_is_available() == 1
+    }
+}
+
+fn check_available() {
+    if !is_available() {
+        panic!("AES-256-GCM is not supported by this CPU");
+    }
+}
+
+unsafe fn encrypt(c: *mut u8,
+                  clen: *mut c_ulonglong,
+                  m: *const u8,
+                  mlen: c_ulonglong,
+                  ad: *const u8,
+                  adlen: c_ulonglong,
+                  nsec: *const [u8; crypto_aead_aes256gcm_NSECBYTES],
+                  npub: *const [u8; NONCEBYTES],
+                  k: *const [u8; KEYBYTES]) -> c_int {
+    check_available();
+    ffi::crypto_aead_aes256gcm_encrypt(c, clen, m, mlen, ad, adlen, nsec, npub, k)
+}
+
+unsafe fn decrypt(m: *mut u8,
+                  mlen: *mut c_ulonglong,
+                  nsec: *mut [u8; crypto_aead_aes256gcm_NSECBYTES],
+                  c: *const u8,
+                  clen: c_ulonglong,
+                  ad: *const u8,
+                  adlen: c_ulonglong,
+                  npub: *const [u8; NONCEBYTES],
+                  k: *const [u8; KEYBYTES]) -> c_int {
+    check_available();
+    ffi::crypto_aead_aes256gcm_decrypt(m, mlen, nsec, c, clen, ad, adlen, npub, k)
+}
+
+unsafe fn encrypt_detached(c: *mut u8,
+                           mac: *mut [u8; TAGBYTES],
+                           maclen_p: *mut c_ulonglong,
+                           m: *const u8,
+                           mlen: c_ulonglong,
+                           ad: *const u8,
+                           adlen: c_ulonglong,
+                           nsec: *const [u8; crypto_aead_aes256gcm_NSECBYTES],
+                           npub: *const [u8; NONCEBYTES],
+                           k: *const [u8; KEYBYTES]) -> c_int {
+    check_available();
+    ffi::crypto_aead_aes256gcm_encrypt_detached(c, mac, maclen_p, m, mlen,
+                                                ad, adlen, nsec, npub, k)
+}
+
+unsafe fn decrypt_detached(m: *mut u8,
+                           nsec: *mut [u8; crypto_aead_aes256gcm_NSECBYTES],
+                           c: *const u8,
+                           clen: c_ulonglong,
+                           mac: *const [u8; TAGBYTES],
+                           ad: *const u8,
+                           adlen: c_ulonglong,
+                           npub: *const [u8; NONCEBYTES],
+                           k: *const [u8; KEYBYTES]) -> c_int {
+    check_available();
+    ffi::crypto_aead_aes256gcm_decrypt_detached(m, nsec, c, clen, mac,
+                                                ad, adlen, npub, k)
+}
+
+/**
+ * Applications that encrypt or decrypt several messages with the same key
+ * can gain speed by expanding the key once with `precompute()` and using
+ * `seal_precomputed()` and `open_precomputed()` instead of `seal()` and
+ * `open()`.
+ *
+ * When a `PrecomputedKey` goes out of scope its contents will be zeroed out
+ */
+pub struct PrecomputedKey(crypto_aead_aes256gcm_state);
+
+impl Drop for PrecomputedKey {
+    fn drop(&mut self) {
+        let &mut PrecomputedKey(ref mut state) = self;
+        unsafe {
+            volatile_set_memory(state.opaque.as_mut_ptr(), 0, state.opaque.len());
+        }
+    }
+}
+
+impl Clone for PrecomputedKey {
+    fn clone(&self) -> PrecomputedKey {
+        let &PrecomputedKey(state) = self;
+        PrecomputedKey(state)
+    }
+}
+
+/**
+ * `precompute()` expands the key `k` into a `PrecomputedKey` that can be
+ * used by `seal_precomputed()` and `open_precomputed()`
+ */
+pub fn precompute(&Key(ref k): &Key) -> PrecomputedKey {
+    check_available();
+    let mut state = crypto_aead_aes256gcm_state {
+        _align: [],
+        opaque: [0u8; ffi::crypto_aead_aes256gcm_STATEBYTES],
+    };
+    unsafe {
+        ffi::crypto_aead_aes256gcm_beforenm(&mut state, k);
+    }
+    PrecomputedKey(state)
+}
+
+/**
+ * `seal_precomputed()` encrypts and authenticates a message `m` together
+ * with optional associated data `ad` using a precomputed key `k` and a
+ * nonce `n`. It returns a ciphertext `c`.
+ */
+pub fn seal_precomputed(m: &[u8],
+                        ad: Option<&[u8]>,
+                        &Nonce(ref n): &Nonce,
+                        &PrecomputedKey(ref k): &PrecomputedKey) -> Vec<u8> {
+    check_available();
+    let (ad_p, ad_len) = ad_ptr_len(ad);
+    let mut c: Vec<u8> = repeat(0u8).take(m.len() + TAGBYTES).collect();
+    let mut clen = 0;
+    unsafe {
+        ffi::crypto_aead_aes256gcm_encrypt_afternm(c.as_mut_ptr(),
+                                                   &mut clen,
+                                                   m.as_ptr(),
+                                                   m.len() as c_ulonglong,
+                                                   ad_p,
+                                                   ad_len,
+                                                   ptr::null(),
+                                                   n,
+                                                   k);
+    }
+    c.truncate(clen as usize);
+    c
+}
+
+/**
+ * `open_precomputed()` verifies and decrypts a ciphertext `c` together with
+ * optional associated data `ad` using a precomputed key `k` and a nonce `n`.
+ * It returns a plaintext `Some(m)`.
+ * If the ciphertext or the associated data fails verification,
+ * `open_precomputed()` returns `None`.
+ */
+pub fn open_precomputed(c: &[u8],
+                        ad: Option<&[u8]>,
+                        &Nonce(ref n): &Nonce,
+                        &PrecomputedKey(ref k): &PrecomputedKey) -> Option<Vec<u8>> {
+    check_available();
+    if c.len() < TAGBYTES {
+        return None
+    }
+    let (ad_p, ad_len) = ad_ptr_len(ad);
+    let mut m: Vec<u8> = repeat(0u8).take(c.len() - TAGBYTES).collect();
+    let mut mlen = 0;
+    let ret = unsafe {
+        ffi::crypto_aead_aes256gcm_decrypt_afternm(m.as_mut_ptr(),
+                                                   &mut mlen,
+                                                   ptr::null_mut(),
+                                                   c.as_ptr(),
+                                                   c.len() as c_ulonglong,
+                                                   ad_p,
+                                                   ad_len,
+                                                   n,
+                                                   k)
+    };
+    if ret == 0 {
+        m.truncate(mlen as usize);
+        Some(m)
+    } else {
+        None
+    }
+}
+
+#[test]
+fn test_vector_1() {
+    // empty message and associated data
+    if !is_available() {
+        return
+    }
+    let k = Key([0xb5,0x2c,0x50,0x5a,0x37,0xd7,0x8e,0xda
+                ,0x5d,0xd3,0x4f,0x20,0xc2,0x25,0x40,0xea
+                ,0x1b,0x58,0x96,0x3c,0xf8,0xe5,0xbf,0x8f
+                ,0xfa,0x85,0xf9,0xf2,0x49,0x25,0x05,0xb4]);
+    let n = Nonce([0x51,0x6c,0x33,0x92,0x9d,0xf5,0xa3,0x28
+                  ,0x4f,0xf4,0x63,0xd7]);
+    let c_expected = vec![0xbd,0xc1,0xac,0x88,0x4d,0x33,0x24,0x57
+                         ,0xa1,0xd2,0x66,0x4f,0x16,0x8c,0x76,0xf0];
+    let c = seal(&[], None, &n, &k);
+    assert!(c == c_expected);
+    assert!(Some(vec![]) == open(&c, None, &n, &k));
+}
+
+#[test]
+fn test_vector_2() {
+    // test case 16 from "The Galois/Counter Mode of Operation (GCM)"
+    if !is_available() {
+        return
+    }
+    let k = Key([0xfe,0xff,0xe9,0x92,0x86,0x65,0x73,0x1c
+                ,0x6d,0x6a,0x8f,0x94,0x67,0x30,0x83,0x08
+                ,0xfe,0xff,0xe9,0x92,0x86,0x65,0x73,0x1c
+                ,0x6d,0x6a,0x8f,0x94,0x67,0x30,0x83,0x08]);
+    let n = Nonce([0xca,0xfe,0xba,0xbe,0xfa,0xce,0xdb,0xad
+                  ,0xde,0xca,0xf8,0x88]);
+    let ad = [0xfe,0xed,0xfa,0xce,0xde,0xad,0xbe,0xef
+             ,0xfe,0xed,0xfa,0xce,0xde,0xad,0xbe,0xef
+             ,0xab,0xad,0xda,0xd2];
+    let m = vec![0xd9,0x31,0x32,0x25,0xf8,0x84,0x06,0xe5
+                ,0xa5,0x59,0x09,0xc5,0xaf,0xf5,0x26,0x9a
+                ,0x86,0xa7,0xa9,0x53,0x15,0x34,0xf7,0xda
+                ,0x2e,0x4c,0x30,0x3d,0x8a,0x31,0x8a,0x72
+                ,0x1c,0x3c,0x0c,0x95,0x95,0x68,0x09,0x53
+                ,0x2f,0xcf,0x0e,0x24,0x49,0xa6,0xb5,0x25
+                ,0xb1,0x6a,0xed,0xf5,0xaa,0x0d,0xe6,0x57
+                ,0xba,0x63,0x7b,0x39];
+    let c_expected = vec![0x52,0x2d,0xc1,0xf0,0x99,0x56,0x7d,0x07
+                         ,0xf4,0x7f,0x37,0xa3,0x2a,0x84,0x42,0x7d
+                         ,0x64,0x3a,0x8c,0xdc,0xbf,0xe5,0xc0,0xc9
+                         ,0x75,0x98,0xa2,0xbd,0x25,0x55,0xd1,0xaa
+                         ,0x8c,0xb0,0x8e,0x48,0x59,0x0d,0xbb,0x3d
+                         ,0xa7,0xb0,0x8b,0x10,0x56,0x82,0x88,0x38
+                         ,0xc5,0xf6,0x1e,0x63,0x93,0xba,0x7a,0x0a
+                         ,0xbc,0xc9,0xf6,0x62
+                         ,0x76,0xfc,0x6e,0xce,0x0f,0x4e,0x17,0x68
+                         ,0xcd,0xdf,0x88,0x53,0xbb,0x2d,0x55,0x1b];
+    let c = seal(&m, Some(&ad[..]), &n, &k);
+    assert!(c == c_expected);
+    let pk = precompute(&k);
+    assert!(c == seal_precomputed(&m, Some(&ad[..]), &n, &pk));
+    assert!(Some(m) == open_precomputed(&c, Some(&ad[..]), &n, &pk));
+}
+
+#[test]
+fn test_seal_open_precomputed() {
+    use randombytes::randombytes;
+    if !is_available() {
+        return
+    }
+    for i in (0..256us) {
+        let k = gen_key();
+        let pk = precompute(&k);
+        let n = gen_nonce();
+        let ad = randombytes(i);
+        let m = randombytes(i);
+        let c = seal(&m, Some(&ad[..]), &n, &k);
+        let c2 = seal_precomputed(&m, Some(&ad[..]), &n, &pk);
+        assert!(c == c2);
+        assert!(Some(m.clone()) == open_precomputed(&c, Some(&ad[..]), &n, &pk));
+        assert!(Some(m) == open(&c2, Some(&ad[..]), &n, &k));
+    }
+}
+
+#[test]
+fn test_seal_open_precomputed_tamper() {
+    use randombytes::randombytes;
+    if !is_available() {
+        return
+    }
+    for i in (0..32us) {
+        let pk = precompute(&gen_key());
+        let n = gen_nonce();
+        let m = randombytes(i);
+        let mut cv = seal_precomputed(&m, None, &n, &pk);
+        let c = cv.as_mut_slice();
+        for j in (0..c.len()) {
+            c[j] ^= 0x20;
+            assert!(None == open_precomputed(c, None, &n, &pk));
+            c[j] ^= 0x20;
+        }
+    }
+}
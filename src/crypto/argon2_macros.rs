@@ -0,0 +1,344 @@
+macro_rules! argon2_module (($pwhash_name:ident,
+                             $str_name:ident,
+                             $str_verify_name:ident,
+                             $alg:expr,
+                             $saltbytes:expr,
+                             $strbytes:expr,
+                             $strprefix:expr,
+                             $opslimit_interactive:expr,
+                             $memlimit_interactive:expr,
+                             $opslimit_moderate:expr,
+                             $memlimit_moderate:expr,
+                             $opslimit_sensitive:expr,
+                             $memlimit_sensitive:expr,
+                             $rehash_alg:ident) => (
+
+use libc::{c_char, c_ulonglong, size_t};
+use std::ops::{Index, Range, RangeFrom, RangeFull, RangeTo};
+use randombytes::randombytes_into;
+use super::rehash;
+
+pub const SALTBYTES: usize = $saltbytes;
+pub const HASHEDPASSWORDBYTES: usize = $strbytes;
+pub const STRPREFIX: &'static str = $strprefix;
+
+/**
+ * `OpsLimit` represents the number of passes over memory to perform when
+ * using the functions in this module.
+ *
+ * A high `OpsLimit` will make the functions
+ * require more CPU cycles
+ */
+#[derive(Copy)]
+pub struct OpsLimit(pub usize);
+
+newtype_clone!(OpsLimit);
+
+/**
+ * `MemLimit` represents the amount of RAM that the functions in this
+ * module will use, in bytes.
+ *
+ * Unlike scrypt, Argon2 always uses exactly this amount of memory, rounded
+ * down to a multiple of 1 kilobyte.
+ */
+#[derive(Copy)]
+pub struct MemLimit(pub usize);
+
+newtype_clone!(MemLimit);
+
+/**
+ * `OPSLIMIT_INTERACTIVE` is a safe baseline for interactive,
+ * online operations.
+ */
+pub const OPSLIMIT_INTERACTIVE: OpsLimit = OpsLimit($opslimit_interactive);
+
+/**
+ * `MEMLIMIT_INTERACTIVE` is a safe baseline for interactive,
+ * online operations.
+ */
+pub const MEMLIMIT_INTERACTIVE: MemLimit = MemLimit($memlimit_interactive);
+
+/**
+ * `OPSLIMIT_MODERATE` and `MEMLIMIT_MODERATE` are a compromise between
+ * `*_INTERACTIVE` and `*_SENSITIVE`, for operations that can afford to
+ * take about a second.
+ */
+pub const OPSLIMIT_MODERATE: OpsLimit = OpsLimit($opslimit_moderate);
+
+/**
+ * `OPSLIMIT_MODERATE` and `MEMLIMIT_MODERATE` are a compromise between
+ * `*_INTERACTIVE` and `*_SENSITIVE`, for operations that can afford to
+ * take about a second.
+ */
+pub const MEMLIMIT_MODERATE: MemLimit = MemLimit($memlimit_moderate);
+
+/**
+ * Using `OPSLIMIT_SENSITIVE` and `MEMLIMIT_SENSITIVE` for highly
+ * sensitive data, e.g. a key protecting other secret keys, makes
+ * deriving a key take several seconds and requires a large amount of
+ * dedicated RAM.
+ */
+pub const OPSLIMIT_SENSITIVE: OpsLimit = OpsLimit($opslimit_sensitive);
+
+/**
+ * Using `OPSLIMIT_SENSITIVE` and `MEMLIMIT_SENSITIVE` for highly
+ * sensitive data, e.g. a key protecting other secret keys, makes
+ * deriving a key take several seconds and requires a large amount of
+ * dedicated RAM.
+ */
+pub const MEMLIMIT_SENSITIVE: MemLimit = MemLimit($memlimit_sensitive);
+
+/**
+ * `Salt` used for password hashing
+ */
+#[derive(Copy)]
+pub struct Salt(pub [u8; SALTBYTES]);
+
+newtype_clone!(Salt);
+newtype_impl!(Salt, SALTBYTES);
+
+/**
+ * `HashedPassword` is a password verifier generated from a password
+ *
+ * A `HashedPassword` is zero-terminated, includes only ASCII characters and
+ * can be conveniently stored into SQL databases and other data stores. No
+ * additional information has to be stored in order to verify the password.
+ */
+#[derive(Copy)]
+pub struct HashedPassword(pub [u8; HASHEDPASSWORDBYTES]);
+
+newtype_clone!(HashedPassword);
+newtype_impl!(HashedPassword, HASHEDPASSWORDBYTES);
+
+/**
+ * `gen_salt()` randomly generates a new `Salt` for key derivation
+ *
+ * THREAD SAFETY: `gen_salt()` is thread-safe provided that you have
+ * called `sodiumoxide::init()` once before using any other function
+ * from sodiumoxide.
+ */
+pub fn gen_salt() -> Salt {
+    let mut salt = [0; SALTBYTES];
+    randombytes_into(&mut salt);
+    Salt(salt)
+}
+
+/**
+ * `derive_key()` derives a key from a password and a `Salt`
+ *
+ * The computed key is stored into key, which has to be at least 16 bytes
+ * long.
+ *
+ * `opslimit` represents the number of passes over memory. Raising this number
+ * will make the function require more CPU cycles to compute a key.
+ *
+ * `memlimit` is the amount of RAM that the function will use, in bytes.
+ *
+ * The function returns `Ok(key)` on success and `Err(())` if the computation
+ * didn't complete, usually because the parameters are out of range or the
+ * operating system refused to allocate the amount of requested memory.
+ */
+pub fn derive_key<'a>(key: &'a mut [u8],
+                      passwd: &[u8],
+                      &Salt(ref sb): &Salt,
+                      OpsLimit(opslimit): OpsLimit,
+                      MemLimit(memlimit): MemLimit) -> Result<&'a [u8], ()> {
+    let res = unsafe {
+        $pwhash_name(key.as_mut_ptr(),
+                     key.len() as c_ulonglong,
+                     passwd.as_ptr() as *const c_char,
+                     passwd.len() as c_ulonglong,
+                     sb,
+                     opslimit as c_ulonglong,
+                     memlimit as size_t,
+                     $alg)
+    };
+    if res == 0 {
+        Ok(key)
+    } else {
+        Err(())
+    }
+}
+
+/**
+ * `pwhash()` returns a `HashedPassword` which
+ * includes:
+ *
+ * - the result of a memory-hard, CPU-intensive hash function applied to the
+ *   password `passwd`
+ * - the automatically generated salt used for the previous computation
+ * - the other parameters required to verify the password: the algorithm
+ *   version, opslimit and memlimit
+ *
+ * `OPSLIMIT_INTERACTIVE` and `MEMLIMIT_INTERACTIVE` are safe baseline
+ * values to use for `opslimit` and `memlimit`.
+ *
+ * The function returns `Ok(hashed_password)` on success and `Err(())` if it
+ * didn't complete successfully
+ */
+pub fn pwhash(passwd: &[u8],
+              OpsLimit(opslimit): OpsLimit,
+              MemLimit(memlimit): MemLimit) -> Result<HashedPassword, ()> {
+    let mut out = HashedPassword([0; HASHEDPASSWORDBYTES]);
+    let res = unsafe {
+        let HashedPassword(ref mut str_) = out;
+        $str_name(str_ as *mut [u8; HASHEDPASSWORDBYTES]
+                       as *mut [c_char; HASHEDPASSWORDBYTES],
+                  passwd.as_ptr() as *const c_char,
+                  passwd.len() as c_ulonglong,
+                  opslimit as c_ulonglong,
+                  memlimit as size_t)
+    };
+    if res == 0 {
+        Ok(out)
+    } else {
+        Err(())
+    }
+}
+
+/**
+ * `pwhash_verify()` verifies that the password `passwd` matches the
+ * `HashedPassword` `hp`. It returns `true` if the verification succeeds,
+ * and `false` on error.
+ */
+pub fn pwhash_verify(&HashedPassword(ref str_): &HashedPassword,
+                     passwd: &[u8]) -> bool {
+    let res = unsafe {
+        $str_verify_name(str_ as *const [u8; HASHEDPASSWORDBYTES]
+                              as *const [c_char; HASHEDPASSWORDBYTES],
+                         passwd.as_ptr() as *const c_char,
+                         passwd.len() as c_ulonglong)
+    };
+    res == 0
+}
+
+/**
+ * `needs_rehash()` parses the stored hashed password `stored`, as produced by
+ * the `pwhash()` function of this module or of any other module of
+ * `crypto::pwhash`, and checks it against the policy given by `opslimit` and
+ * `memlimit`.
+ *
+ * `stored` may be followed by zero bytes, so the contents of any
+ * `HashedPassword` can be passed directly.
+ *
+ * The function returns `Ok(true)` if the password should be hashed again with
+ * this module, i.e. if it was hashed with a different algorithm, with a
+ * version of Argon2 older than 1.3, or with a smaller opslimit or memlimit
+ * than the given ones. It returns `Ok(false)` if the stored hash is at least
+ * as strong as the policy and `Err(())` if `stored` cannot be parsed.
+ */
+pub fn needs_rehash(stored: &[u8],
+                    OpsLimit(opslimit): OpsLimit,
+                    MemLimit(memlimit): MemLimit) -> Result<bool, ()> {
+    rehash::needs_rehash(stored, |p| {
+        rehash::argon2_is_weaker(p, rehash::Algorithm::$rehash_alg,
+                                 opslimit, memlimit)
+    })
+}
+
+/**
+ * `verify_and_upgrade()` verifies that the password `passwd` matches the
+ * stored hashed password `stored`, and hashes it again with this module
+ * using `opslimit` and `memlimit` if `needs_rehash()` says so.
+ *
+ * The function returns `Ok(Some(hashed_password))` if the password is correct
+ * and the stored hash has to be replaced with `hashed_password`, and
+ * `Ok(None)` if the password is correct and the stored hash is fine as is.
+ * It returns `Err(())` if the password doesn't match, if `stored` cannot be
+ * parsed or if computing the new hash didn't complete successfully.
+ */
+pub fn verify_and_upgrade(stored: &[u8],
+                          passwd: &[u8],
+                          opslimit: OpsLimit,
+                          memlimit: MemLimit)
+                          -> Result<Option<HashedPassword>, ()> {
+    let (OpsLimit(o), MemLimit(m)) = (opslimit, memlimit);
+    rehash::verify_and_upgrade(stored, passwd, |p| {
+        rehash::argon2_is_weaker(p, rehash::Algorithm::$rehash_alg, o, m)
+    }, || pwhash(passwd, opslimit, memlimit))
+}
+
+#[cfg(test)]
+fn hashed_password_from_str(s: &[u8]) -> HashedPassword {
+    let mut str_ = [0; HASHEDPASSWORDBYTES];
+    for (d, &c) in str_.iter_mut().zip(s.iter()) {
+        *d = c;
+    }
+    HashedPassword(str_)
+}
+
+#[test]
+fn test_derive_key_short() {
+    let mut kb = [0u8; 15];
+    let salt = gen_salt();
+    assert!(derive_key(&mut kb, b"password", &salt,
+                       OpsLimit(3), MemLimit(262144)).is_err());
+}
+
+#[test]
+fn test_derive_key_salt() {
+    let mut kb1 = [0u8; 32];
+    let mut kb2 = [0u8; 32];
+    let salt1 = gen_salt();
+    let salt2 = gen_salt();
+    let pw = b"Correct Horse Battery Staple";
+    let key1 = derive_key(&mut kb1, pw, &salt1,
+                          OpsLimit(3), MemLimit(262144)).unwrap();
+    let key2 = derive_key(&mut kb2, pw, &salt2,
+                          OpsLimit(3), MemLimit(262144)).unwrap();
+    assert!(key1 != key2);
+}
+
+#[test]
+fn test_pwhash_verify() {
+    use randombytes::randombytes;
+    for i in (0..32us) {
+        let pw = randombytes(i);
+        let pwh = pwhash(&pw, OpsLimit(3), MemLimit(262144)).unwrap();
+        assert!(&pwh[..STRPREFIX.len()] == STRPREFIX.as_bytes());
+        assert!(pwhash_verify(&pwh, &pw));
+    }
+}
+
+#[test]
+fn test_pwhash_verify_tamper() {
+    use randombytes::randombytes;
+    for i in (0..16us) {
+        let mut pw = randombytes(i);
+        let pwh = pwhash(&pw, OpsLimit(3), MemLimit(262144)).unwrap();
+        for j in (0..pw.len()) {
+            pw[j] ^= 0x20;
+            assert!(!pwhash_verify(&pwh, &pw));
+            pw[j] ^= 0x20;
+        }
+    }
+}
+
+#[test]
+fn test_pwhash_verify_interactive() {
+    let pw = b"Correct Horse Battery Staple";
+    let pwh = pwhash(pw, OPSLIMIT_INTERACTIVE, MEMLIMIT_INTERACTIVE).unwrap();
+    assert!(pwhash_verify(&pwh, pw));
+}
+
+#[test]
+fn test_pwhash_verify_garbage() {
+    let pwh = hashed_password_from_str(STRPREFIX.as_bytes());
+    assert!(!pwhash_verify(&pwh, b""));
+}
+
+#[cfg(test)]
+mod bench {
+    extern crate test;
+    use super::*;
+
+    #[bench]
+    fn bench_pwhash_interactive(b: &mut test::Bencher) {
+        let pw = b"Correct Horse Battery Staple";
+        b.iter(|| {
+            pwhash(pw, OPSLIMIT_INTERACTIVE, MEMLIMIT_INTERACTIVE)
+        });
+    }
+}
+
+));
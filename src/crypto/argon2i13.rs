@@ -0,0 +1,57 @@
+/*!
+`crypto_pwhash_argon2i`, Argon2i version 1.3, the variant of Argon2 with
+data-independent memory accesses, which resists side-channel attacks.
+*/
+use ffi::{crypto_pwhash_argon2i,
+          crypto_pwhash_argon2i_str,
+          crypto_pwhash_argon2i_str_verify,
+          crypto_pwhash_argon2i_ALG_ARGON2I13,
+          crypto_pwhash_argon2i_SALTBYTES,
+          crypto_pwhash_argon2i_STRBYTES,
+          crypto_pwhash_argon2i_STRPREFIX,
+          crypto_pwhash_argon2i_OPSLIMIT_INTERACTIVE,
+          crypto_pwhash_argon2i_MEMLIMIT_INTERACTIVE,
+          crypto_pwhash_argon2i_OPSLIMIT_MODERATE,
+          crypto_pwhash_argon2i_MEMLIMIT_MODERATE,
+          crypto_pwhash_argon2i_OPSLIMIT_SENSITIVE,
+          crypto_pwhash_argon2i_MEMLIMIT_SENSITIVE};
+
+argon2_module!(crypto_pwhash_argon2i,
+               crypto_pwhash_argon2i_str,
+               crypto_pwhash_argon2i_str_verify,
+               crypto_pwhash_argon2i_ALG_ARGON2I13,
+               crypto_pwhash_argon2i_SALTBYTES,
+               crypto_pwhash_argon2i_STRBYTES,
+               crypto_pwhash_argon2i_STRPREFIX,
+               crypto_pwhash_argon2i_OPSLIMIT_INTERACTIVE,
+               crypto_pwhash_argon2i_MEMLIMIT_INTERACTIVE,
+               crypto_pwhash_argon2i_OPSLIMIT_MODERATE,
+               crypto_pwhash_argon2i_MEMLIMIT_MODERATE,
+               crypto_pwhash_argon2i_OPSLIMIT_SENSITIVE,
+               crypto_pwhash_argon2i_MEMLIMIT_SENSITIVE,
+               Argon2i);
+
+#[test]
+fn test_derive_key() {
+    let mut kb = [0u8; 32];
+    let salt = Salt([0x10,0x11,0x12,0x13,0x14,0x15,0x16,0x17
+                    ,0x18,0x19,0x1a,0x1b,0x1c,0x1d,0x1e,0x1f]);
+    let pw = b"Correct Horse Battery Staple";
+    // computed with the argon2i reference implementation, t = 3, m = 256 KiB, p = 1
+    let key_expected = [0x8d,0x3e,0x84,0xfa,0x0e,0xde,0x47,0xc9
+                       ,0x42,0x93,0x96,0x8b,0xf6,0x2a,0x46,0xdd
+                       ,0xb0,0x04,0x24,0x2a,0xef,0xe1,0xfb,0x5e
+                       ,0x85,0x46,0x75,0x58,0xab,0x30,0x34,0xd8];
+    let key = derive_key(&mut kb, pw, &salt,
+                         OpsLimit(3), MemLimit(262144)).unwrap();
+    assert!(key == &key_expected[..]);
+}
+
+#[test]
+fn test_pwhash_verify_known() {
+    // generated with crypto_pwhash_argon2i_str() from libsodium
+    let pwh = hashed_password_from_str(
+        b"$argon2i$v=19$m=256,t=3,p=1$WmUDK4uiESBuX720nxziUQ$Mz3BNn0SL6K8y7gFOgCDdoeQ/rO0fCyMXxy6NnkOsio");
+    assert!(pwhash_verify(&pwh, b"Correct Horse Battery Staple"));
+    assert!(!pwhash_verify(&pwh, b"Correct Horse Battery Stapler"));
+}
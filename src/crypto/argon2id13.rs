@@ -0,0 +1,58 @@
+/*!
+`crypto_pwhash_argon2id`, Argon2id version 1.3, the hybrid variant of
+Argon2 that combines data-independent and data-dependent memory accesses.
+This is the recommended variant for password hashing.
+*/
+use ffi::{crypto_pwhash_argon2id,
+          crypto_pwhash_argon2id_str,
+          crypto_pwhash_argon2id_str_verify,
+          crypto_pwhash_argon2id_ALG_ARGON2ID13,
+          crypto_pwhash_argon2id_SALTBYTES,
+          crypto_pwhash_argon2id_STRBYTES,
+          crypto_pwhash_argon2id_STRPREFIX,
+          crypto_pwhash_argon2id_OPSLIMIT_INTERACTIVE,
+          crypto_pwhash_argon2id_MEMLIMIT_INTERACTIVE,
+          crypto_pwhash_argon2id_OPSLIMIT_MODERATE,
+          crypto_pwhash_argon2id_MEMLIMIT_MODERATE,
+          crypto_pwhash_argon2id_OPSLIMIT_SENSITIVE,
+          crypto_pwhash_argon2id_MEMLIMIT_SENSITIVE};
+
+argon2_module!(crypto_pwhash_argon2id,
+               crypto_pwhash_argon2id_str,
+               crypto_pwhash_argon2id_str_verify,
+               crypto_pwhash_argon2id_ALG_ARGON2ID13,
+               crypto_pwhash_argon2id_SALTBYTES,
+               crypto_pwhash_argon2id_STRBYTES,
+               crypto_pwhash_argon2id_STRPREFIX,
+               crypto_pwhash_argon2id_OPSLIMIT_INTERACTIVE,
+               crypto_pwhash_argon2id_MEMLIMIT_INTERACTIVE,
+               crypto_pwhash_argon2id_OPSLIMIT_MODERATE,
+               crypto_pwhash_argon2id_MEMLIMIT_MODERATE,
+               crypto_pwhash_argon2id_OPSLIMIT_SENSITIVE,
+               crypto_pwhash_argon2id_MEMLIMIT_SENSITIVE,
+               Argon2id);
+
+#[test]
+fn test_derive_key() {
+    let mut kb = [0u8; 32];
+    let salt = Salt([0x10,0x11,0x12,0x13,0x14,0x15,0x16,0x17
+                    ,0x18,0x19,0x1a,0x1b,0x1c,0x1d,0x1e,0x1f]);
+    let pw = b"Correct Horse Battery Staple";
+    // computed with the argon2id reference implementation, t = 3, m = 256 KiB, p = 1
+    let key_expected = [0x2e,0x1b,0x0e,0xc4,0xe9,0xef,0x1c,0xb1
+                       ,0xb3,0x1e,0x7c,0x3f,0x8f,0xb8,0xc7,0x15
+                       ,0x8b,0x34,0xf5,0xef,0xde,0x1f,0x7f,0xf8
+                       ,0x1d,0x69,0xce,0xf3,0xcf,0x70,0xcc,0x90];
+    let key = derive_key(&mut kb, pw, &salt,
+                         OpsLimit(3), MemLimit(262144)).unwrap();
+    assert!(key == &key_expected[..]);
+}
+
+#[test]
+fn test_pwhash_verify_known() {
+    // generated with crypto_pwhash_argon2id_str() from libsodium
+    let pwh = hashed_password_from_str(
+        b"$argon2id$v=19$m=256,t=3,p=1$CHpVvNPYdHW46pV04gCZgA$IKP/Y9FXxcph+//VY6bIGkXi7o1QTYJPg1yYQq17e6g");
+    assert!(pwhash_verify(&pwh, b"Correct Horse Battery Staple"));
+    assert!(!pwhash_verify(&pwh, b"Correct Horse Battery Stapler"));
+}
@@ -45,7 +45,29 @@ combination of Curve25519, Salsa20, and Poly1305 specified in
 This function is conjectured to meet the standard notions of privacy and
 third-party unforgeability.
 
+# Alternate primitives
+`crypto_box_curve25519xchacha20poly1305` (in
+`asymmetricbox::curve25519xchacha20poly1305`) has the same API and key and
+nonce sizes, but uses XChaCha20 instead of XSalsa20. It is compatible with
+libsodium's `crypto_box_curve25519xchacha20poly1305_*` functions.
+
+# Detached mode
+`seal_detached()` and `seal_detached_precomputed()` encrypt a message in place
+and return the authentication tag separately as a `Tag`, instead of
+prepending it to the ciphertext. `open_detached()` and
+`open_detached_precomputed()` verify the tag and decrypt the ciphertext in
+place.
+
+# In-place operation
+`seal_inplace()`, `open_inplace()` and their `_precomputed_inplace()`
+counterparts work on a `Vec<u8>` which holds the message or the ciphertext, so
+that large payloads can be processed without copying them into a new buffer.
 */
 pub use self::curve25519xsalsa20poly1305::*;
+#[path="box_macros.rs"]
+#[macro_use]
+mod box_macros;
 #[path="curve25519xsalsa20poly1305.rs"]
 pub mod curve25519xsalsa20poly1305;
+#[path="curve25519xchacha20poly1305.rs"]
+pub mod curve25519xchacha20poly1305;
@@ -17,6 +17,19 @@ perhaps one valid authenticator can be converted into another valid
 authenticator for the same message. NaCl also does not make any promises
 regarding "truncated unforgeability."
 
+# Incremental authentication
+`State` computes the authenticator of a message that is passed to it in
+several chunks, e.g. when the message is too large to keep it in memory.
+`State::finalize()` returns the same `Tag` as `authenticate()` would for the
+concatenation of all chunks, and `State::verify()` checks it against a given
+tag.
+
+# Keys of arbitrary length
+HMAC is defined for keys of any length. `authenticate_with_key_slice()`,
+`verify_with_key_slice()` and `State::init_with_key_slice()` accept the key
+as a plain byte slice, e.g. for protocols that hand out keys that are not
+exactly `KEYBYTES` long.
+
 # Selected primitive
 `authenticate()` is currently an implementation of
 `HMAC-SHA-512-256`, i.e., the first 256 bits of `HMAC-SHA-512`.
@@ -30,6 +43,7 @@ NaCl supports the following secret-key authentication functions:
 |crypto_auth              |primitive        |BYTES|KEYBYTES|
 |-------------------------|-----------------|-----|--------|
 |crypto_auth_hmacsha256   |HMAC_SHA-256     |32   |32      |
+|crypto_auth_hmacsha512   |HMAC_SHA-512     |64   |32      |
 |crypto_auth_hmacsha512256|HMAC_SHA-512-256 |32   |32      |
 ------------------------------------------------------------
 */
@@ -41,3 +55,5 @@ mod auth_macros;
 pub mod hmacsha512256;
 #[path="hmacsha256.rs"]
 pub mod hmacsha256;
+#[path="hmacsha512.rs"]
+pub mod hmacsha512;
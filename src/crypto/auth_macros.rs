@@ -1,11 +1,18 @@
 macro_rules! auth_module (($auth_name:ident, 
                            $verify_name:ident, 
                            $verify_fn:ident, 
+                           $state_name:ident,
+                           $init_name:ident,
+                           $update_name:ident,
+                           $final_name:ident,
                            $keybytes:expr, 
                            $tagbytes:expr) => (
 
 use libc::c_ulonglong;
 use std::intrinsics::volatile_set_memory;
+use std::io;
+use std::io::Write;
+use std::mem;
 use std::ops::{Index, Range, RangeFrom, RangeFull, RangeTo};
 use randombytes::randombytes_into;
 
@@ -28,7 +35,7 @@ newtype_impl!(Key, KEYBYTES);
   * Authentication `Tag`
   *
   * The tag implements the traits `PartialEq` and `Eq` using constant-time
-  * comparison functions. See `sodiumoxide::crypto::verify::verify_32`
+  * comparison functions. See `sodiumoxide::crypto::verify`
   */
 #[derive(Copy)]
 pub struct Tag(pub [u8; TAGBYTES]);
@@ -88,6 +95,88 @@ pub fn verify(&Tag(ref tag): &Tag, m: &[u8],
     }
 }
 
+/**
+ * `State` for incremental authentication
+ *
+ * A `State` is initialized with a secret key, fed the message in chunks
+ * using `update()` and finally turned into an authenticator tag using
+ * `finalize()` or checked against a given tag using `verify()`.
+ *
+ * When a `State` goes out of scope its contents will be zeroed out
+ */
+pub struct State($state_name);
+
+impl State {
+    /**
+     * `init()` initializes a new `State` with the secret key `k`
+     */
+    pub fn init(&Key(ref k): &Key) -> State {
+        unsafe {
+            let mut st: $state_name = mem::zeroed();
+            $init_name(&mut st, k);
+            State(st)
+        }
+    }
+
+    /**
+     * `update()` feeds the next chunk `data` of the message into the `State`
+     */
+    pub fn update(&mut self, data: &[u8]) {
+        let &mut State(ref mut st) = self;
+        unsafe {
+            $update_name(st, data.as_ptr(), data.len() as c_ulonglong);
+        }
+    }
+
+    /**
+     * `finalize()` finishes the computation and returns the authenticator
+     * tag of all data passed to `update()`
+     */
+    pub fn finalize(mut self) -> Tag {
+        let &mut State(ref mut st) = &mut self;
+        let mut tag = [0; TAGBYTES];
+        unsafe {
+            $final_name(st, &mut tag);
+        }
+        Tag(tag)
+    }
+
+    /**
+     * `verify()` finishes the computation and returns `true` if `tag` is a
+     * correct authenticator of all data passed to `update()`. Otherwise it
+     * returns false. The comparison is done in constant time.
+     */
+    pub fn verify(self, tag: &Tag) -> bool {
+        self.finalize() == *tag
+    }
+}
+
+/**
+ * Writing to a `State` feeds the data into the authenticator, so that
+ * `io::copy()` can be used to authenticate anything that implements
+ * `io::Read`.
+ */
+impl Write for State {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Drop for State {
+    fn drop(&mut self) {
+        let &mut State(ref mut st) = self;
+        unsafe {
+            volatile_set_memory(st as *mut $state_name as *mut u8, 0,
+                                mem::size_of::<$state_name>());
+        }
+    }
+}
+
 #[test]
 fn test_auth_verify() {
     use randombytes::randombytes;
@@ -120,6 +209,43 @@ fn test_auth_verify_tamper() {
     }
 }
 
+#[test]
+fn test_state_random_chunks() {
+    use randombytes::randombytes;
+    for i in (0..256us) {
+        let k = gen_key();
+        let m = randombytes(i);
+        let mut state = State::init(&k);
+        let mut pos = 0;
+        while pos < m.len() {
+            let chunk = randombytes(1)[0] as usize % 200;
+            let end = if pos + chunk > m.len() { m.len() } else { pos + chunk };
+            state.update(&m[pos..end]);
+            pos = end;
+        }
+        let tag = state.finalize();
+        assert!(tag == authenticate(&m, &k));
+    }
+}
+
+#[test]
+fn test_state_verify() {
+    use randombytes::randombytes;
+    for i in (0..32us) {
+        let k = gen_key();
+        let m = randombytes(i);
+        let Tag(mut tagbuf) = authenticate(&m, &k);
+        let mut state = State::init(&k);
+        state.update(&m[..i / 2]);
+        state.write_all(&m[i / 2..]).unwrap();
+        assert!(state.verify(&Tag(tagbuf)));
+        let mut state2 = State::init(&k);
+        state2.update(&m);
+        tagbuf[0] ^= 0x20;
+        assert!(!state2.verify(&Tag(tagbuf)));
+    }
+}
+
 #[cfg(test)]
 mod bench {
     extern crate test;
@@ -142,6 +268,21 @@ mod bench {
         });
     }
 
+    #[bench]
+    fn bench_state(b: &mut test::Bencher) {
+        let k = gen_key();
+        let ms: Vec<Vec<u8>> = BENCH_SIZES.iter().map(|s| {
+            randombytes(*s)
+        }).collect();
+        b.iter(|| {
+            let mut state = State::init(&k);
+            for m in ms.iter() {
+                state.update(&m);
+            }
+            state.finalize();
+        });
+    }
+
     #[bench]
     fn bench_verify(b: &mut test::Bencher) {
         let k = gen_key();
@@ -160,3 +301,137 @@ mod bench {
 }
 
 ));
+
+/* `hmac_module!` adds the functions for keys of arbitrary length and a
+   `Clone` impl for `State` to a module that has been created with
+   `auth_module!` for an HMAC primitive. The module has to import
+   `libc::size_t` and, for the tests, `rustc_serialize`.
+   `$rfc4231_column` is the column of testvectors/hmac_rfc4231.input that
+   holds the tags of the primitive, which are truncated to `TAGBYTES`.
+   `auth_module!` itself doesn't implement `Clone`, since cloning a one-time
+   authenticator's `State` would allow authenticating two messages with the
+   same key. */
+macro_rules! hmac_module (($state_name:ident,
+                           $init_name:ident,
+                           $rfc4231_column:expr) => (
+
+/**
+ * `authenticate_with_key_slice()` authenticates a message `m` using a
+ * secret key `k` of arbitrary length, as HMAC allows.
+ * The function returns an authenticator tag.
+ *
+ * Keys longer than the block size of the hash function are hashed first.
+ * Keys of length `KEYBYTES` give the same result as `authenticate()`.
+ */
+pub fn authenticate_with_key_slice(m: &[u8], k: &[u8]) -> Tag {
+    let mut state = State::init_with_key_slice(k);
+    state.update(m);
+    state.finalize()
+}
+
+/**
+ * `verify_with_key_slice()` returns `true` if `tag` is a correct
+ * authenticator of message `m` under a secret key `k` of arbitrary length.
+ * Otherwise it returns false.
+ */
+pub fn verify_with_key_slice(tag: &Tag, m: &[u8], k: &[u8]) -> bool {
+    let mut state = State::init_with_key_slice(k);
+    state.update(m);
+    state.verify(tag)
+}
+
+impl State {
+    /**
+     * `init_with_key_slice()` initializes a new `State` with the secret key
+     * `k` of arbitrary length
+     */
+    pub fn init_with_key_slice(k: &[u8]) -> State {
+        unsafe {
+            let mut st: $state_name = mem::zeroed();
+            $init_name(&mut st, k.as_ptr(), k.len() as size_t);
+            State(st)
+        }
+    }
+}
+
+/**
+ * Cloning a `State` allows authenticating several messages that share a
+ * common prefix without processing the prefix again.
+ */
+impl Clone for State {
+    fn clone(&self) -> State {
+        let &State(st) = self;
+        State(st)
+    }
+}
+
+#[test]
+fn test_key_slice() {
+    use randombytes::randombytes;
+    for i in (0..256us) {
+        let k = gen_key();
+        let m = randombytes(i);
+        let tag = authenticate(&m, &k);
+        let Key(ref kb) = k;
+        assert!(authenticate_with_key_slice(&m, kb) == tag);
+        assert!(verify_with_key_slice(&tag, &m, kb));
+        let other_key = randombytes(i);
+        let tag = authenticate_with_key_slice(&m, &other_key);
+        assert!(verify_with_key_slice(&tag, &m, &other_key));
+        assert!(!verify_with_key_slice(&tag, &m, kb));
+    }
+}
+
+#[test]
+fn test_state_clone() {
+    use randombytes::randombytes;
+    for i in (0..32us) {
+        let k = gen_key();
+        let m1 = randombytes(i);
+        let m2 = randombytes(i);
+        let mut state1 = State::init(&k);
+        state1.update(&m1);
+        let mut state2 = state1.clone();
+        state1.update(&m2);
+        state2.update(&m1);
+        let mut m12 = m1.clone();
+        m12.push_all(&m2);
+        let mut m11 = m1.clone();
+        m11.push_all(&m1);
+        assert!(state1.finalize() == authenticate(&m12, &k));
+        assert!(state2.finalize() == authenticate(&m11, &k));
+    }
+}
+
+#[test]
+fn test_rfc4231_vectors() {
+    // RFC 4231, test cases 1 to 7; test case 5 is truncated to 128 bits
+    use self::rustc_serialize::hex::FromHex;
+    use std::old_io::BufferedReader;
+    use std::old_io::File;
+    use std::path::Path;
+
+    let p = &Path::new("testvectors/hmac_rfc4231.input");
+    let mut r = BufferedReader::new(File::open(p).unwrap());
+    loop {
+        let line = match r.read_line() {
+            Err(_) => break,
+            Ok(line) => line
+        };
+        let x: Vec<&str> = line.split(':').collect();
+        let k = x[0].from_hex().unwrap();
+        let m = x[1].from_hex().unwrap();
+        let mut a_expected = x[$rfc4231_column].from_hex().unwrap();
+        a_expected.truncate(TAGBYTES);
+        let Tag(a) = authenticate_with_key_slice(&m, &k);
+        assert!(&a[..a_expected.len()] == &a_expected[..]);
+        let mut state = State::init_with_key_slice(&k);
+        for chunk in m.chunks(13) {
+            state.update(chunk);
+        }
+        let Tag(a) = state.finalize();
+        assert!(&a[..a_expected.len()] == &a_expected[..]);
+    }
+}
+
+));
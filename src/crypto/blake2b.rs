@@ -0,0 +1,554 @@
+/*!
+`crypto_generichash_blake2b`, the BLAKE2b hash function with support for
+keys, salts and personalization strings as specified in
+[RFC 7693](https://tools.ietf.org/html/rfc7693).
+*/
+#[cfg(test)]
+extern crate "rustc-serialize" as rustc_serialize;
+use ffi;
+use libc::{c_ulonglong, size_t};
+use std::intrinsics::volatile_set_memory;
+use std::io;
+use std::io::Write;
+use std::iter::repeat;
+use std::ops::{Index, Range, RangeFrom, RangeFull, RangeTo};
+use std::ptr;
+use randombytes::randombytes_into;
+
+pub const BYTES_MIN: usize = ffi::crypto_generichash_blake2b_BYTES_MIN;
+pub const BYTES_MAX: usize = ffi::crypto_generichash_blake2b_BYTES_MAX;
+pub const BYTES: usize = ffi::crypto_generichash_blake2b_BYTES;
+pub const KEYBYTES_MIN: usize = ffi::crypto_generichash_blake2b_KEYBYTES_MIN;
+pub const KEYBYTES_MAX: usize = ffi::crypto_generichash_blake2b_KEYBYTES_MAX;
+pub const KEYBYTES: usize = ffi::crypto_generichash_blake2b_KEYBYTES;
+pub const SALTBYTES: usize = ffi::crypto_generichash_blake2b_SALTBYTES;
+pub const PERSONALBYTES: usize = ffi::crypto_generichash_blake2b_PERSONALBYTES;
+
+/**
+ * `Digest` of a message
+ *
+ * The length of a `Digest` is chosen when computing it and lies between
+ * `BYTES_MIN` and `BYTES_MAX`.
+ *
+ * `Digest` implements the traits `PartialEq` and `Eq` using constant-time
+ * comparison functions, so that digests computed with a key can be
+ * compared safely.
+ */
+#[derive(Copy)]
+pub struct Digest {
+    len: usize,
+    data: [u8; BYTES_MAX],
+}
+
+impl Digest {
+    /**
+     * `from_slice()` creates a `Digest` from a byte slice, e.g. to compare
+     * a received digest in constant time against a computed one
+     *
+     * This function will fail and return None if the length of the
+     * byte-slice isn't between `BYTES_MIN` and `BYTES_MAX`
+     */
+    pub fn from_slice(bs: &[u8]) -> Option<Digest> {
+        if bs.len() < BYTES_MIN || bs.len() > BYTES_MAX {
+            return None
+        }
+        let mut digest = Digest { len: bs.len(), data: [0; BYTES_MAX] };
+        for (d, &b) in digest.data.iter_mut().zip(bs.iter()) {
+            *d = b;
+        }
+        Some(digest)
+    }
+
+    /**
+     * `len()` returns the length of the `Digest` in bytes
+     */
+    pub fn len(&self) -> usize {
+        self.len
+    }
+}
+
+impl Clone for Digest {
+    fn clone(&self) -> Digest {
+        *self
+    }
+}
+
+impl Eq for Digest {}
+
+impl PartialEq for Digest {
+    fn eq(&self, other: &Digest) -> bool {
+        self.len == other.len && unsafe {
+            ffi::sodium_memcmp(self.data.as_ptr(),
+                               other.data.as_ptr(),
+                               self.len as size_t) == 0
+        }
+    }
+}
+
+/**
+ * Allows a user to access the byte contents of a `Digest` as a slice.
+ */
+impl Index<Range<usize>> for Digest {
+    type Output = [u8];
+    fn index(&self, _index: &Range<usize>) -> &[u8] {
+        self.data[..self.len].index(_index)
+    }
+}
+
+/**
+ * Allows a user to access the byte contents of a `Digest` as a slice.
+ */
+impl Index<RangeTo<usize>> for Digest {
+    type Output = [u8];
+    fn index(&self, _index: &RangeTo<usize>) -> &[u8] {
+        self.data[..self.len].index(_index)
+    }
+}
+
+/**
+ * Allows a user to access the byte contents of a `Digest` as a slice.
+ */
+impl Index<RangeFrom<usize>> for Digest {
+    type Output = [u8];
+    fn index(&self, _index: &RangeFrom<usize>) -> &[u8] {
+        self.data[..self.len].index(_index)
+    }
+}
+
+/**
+ * Allows a user to access the byte contents of a `Digest` as a slice.
+ */
+impl Index<RangeFull> for Digest {
+    type Output = [u8];
+    fn index(&self, _index: &RangeFull) -> &[u8] {
+        self.data[..self.len].index(_index)
+    }
+}
+
+/**
+ * `Salt` for hashing
+ *
+ * The salt makes the output of `hash_salt_personal()` differ from the
+ * output for the same message and key with a different salt.
+ */
+#[derive(Copy)]
+pub struct Salt(pub [u8; SALTBYTES]);
+
+newtype_clone!(Salt);
+newtype_impl!(Salt, SALTBYTES);
+
+/**
+ * `Personal` is a personalization string for hashing
+ *
+ * Different applications can use different personalization strings to
+ * make sure that they never compute the same digests, even for the same
+ * message and key.
+ */
+#[derive(Copy)]
+pub struct Personal(pub [u8; PERSONALBYTES]);
+
+newtype_clone!(Personal);
+newtype_impl!(Personal, PERSONALBYTES);
+
+/**
+ * `Key` of the recommended length `KEYBYTES` for keyed hashing
+ *
+ * The hash functions take keys of any length between `KEYBYTES_MIN` and
+ * `KEYBYTES_MAX` as byte slices, so a `Key` is passed as `Some(&key[..])`.
+ *
+ * When a `Key` goes out of scope its contents
+ * will be zeroed out
+ */
+pub struct Key(pub [u8; KEYBYTES]);
+
+newtype_drop!(Key);
+newtype_clone!(Key);
+newtype_impl!(Key, KEYBYTES);
+
+/**
+ * `gen_key()` randomly generates a key of the recommended length `KEYBYTES`
+ *
+ * THREAD SAFETY: `gen_key()` is thread-safe provided that you have
+ * called `sodiumoxide::init()` once before using any other function
+ * from sodiumoxide.
+ */
+pub fn gen_key() -> Key {
+    let mut k = [0; KEYBYTES];
+    randombytes_into(&mut k);
+    Key(k)
+}
+
+/**
+ * `gen_salt()` randomly generates a salt
+ *
+ * THREAD SAFETY: `gen_salt()` is thread-safe provided that you have
+ * called `sodiumoxide::init()` once before using any other function
+ * from sodiumoxide.
+ */
+pub fn gen_salt() -> Salt {
+    let mut salt = [0; SALTBYTES];
+    randombytes_into(&mut salt);
+    Salt(salt)
+}
+
+fn check_params(out_len: usize, key: Option<&[u8]>)
+                -> Result<(*const u8, size_t), ()> {
+    if out_len < BYTES_MIN || out_len > BYTES_MAX {
+        return Err(())
+    }
+    match key {
+        Some(k) if k.len() < KEYBYTES_MIN || k.len() > KEYBYTES_MAX => Err(()),
+        Some(k) => Ok((k.as_ptr(), k.len() as size_t)),
+        None => Ok((ptr::null(), 0))
+    }
+}
+
+/**
+ * `hash()` hashes a message `m` with an optional key `key` and returns a
+ * `Digest` of length `out_len`.
+ *
+ * `hash()` returns `Err(())` if `out_len` is not between `BYTES_MIN` and
+ * `BYTES_MAX` or if the length of `key` is not between `KEYBYTES_MIN` and
+ * `KEYBYTES_MAX`.
+ */
+pub fn hash(m: &[u8],
+            out_len: usize,
+            key: Option<&[u8]>) -> Result<Digest, ()> {
+    let (key_p, key_len) = try!(check_params(out_len, key));
+    let mut digest = Digest { len: out_len, data: [0; BYTES_MAX] };
+    unsafe {
+        ffi::crypto_generichash_blake2b(digest.data.as_mut_ptr(),
+                                        out_len as size_t,
+                                        m.as_ptr(),
+                                        m.len() as c_ulonglong,
+                                        key_p,
+                                        key_len);
+    }
+    Ok(digest)
+}
+
+/**
+ * `hash_salt_personal()` hashes a message `m` like `hash()`, but also takes
+ * an optional salt `salt` and an optional personalization string `personal`
+ * into account. Passing `None` is equivalent to passing all-zero values.
+ */
+pub fn hash_salt_personal(m: &[u8],
+                          out_len: usize,
+                          key: Option<&[u8]>,
+                          salt: Option<&Salt>,
+                          personal: Option<&Personal>) -> Result<Digest, ()> {
+    let (key_p, key_len) = try!(check_params(out_len, key));
+    let Salt(ref s) = *salt.unwrap_or(&Salt([0; SALTBYTES]));
+    let Personal(ref p) = *personal.unwrap_or(&Personal([0; PERSONALBYTES]));
+    let mut digest = Digest { len: out_len, data: [0; BYTES_MAX] };
+    unsafe {
+        ffi::crypto_generichash_blake2b_salt_personal(digest.data.as_mut_ptr(),
+                                                      out_len as size_t,
+                                                      m.as_ptr(),
+                                                      m.len() as c_ulonglong,
+                                                      key_p,
+                                                      key_len,
+                                                      s,
+                                                      p);
+    }
+    Ok(digest)
+}
+
+/**
+ * `State` for incremental hashing
+ *
+ * A `State` can be cloned in the middle of a computation, for example to
+ * compute the digests of several messages that share a common prefix.
+ *
+ * When a `State` goes out of scope its contents will be zeroed out
+ */
+pub struct State {
+    buf: Vec<u8>,
+    offset: usize,
+    out_len: usize,
+}
+
+// libsodium declares crypto_generichash_blake2b_state with 64-byte
+// alignment, which Rust types cannot express. The state is therefore kept in
+// an oversized heap buffer, which doesn't move when the `State` is moved, at
+// the first 64-byte aligned offset.
+const STATEALIGN: usize = 64;
+
+fn alloc_state() -> (Vec<u8>, usize) {
+    let len = ffi::crypto_generichash_blake2b_STATEBYTES + STATEALIGN - 1;
+    let buf: Vec<u8> = repeat(0u8).take(len).collect();
+    let misalignment = buf.as_ptr() as usize % STATEALIGN;
+    let offset = (STATEALIGN - misalignment) % STATEALIGN;
+    (buf, offset)
+}
+
+impl State {
+    fn st(&mut self) -> *mut ffi::crypto_generichash_blake2b_state {
+        unsafe {
+            self.buf.as_mut_ptr().offset(self.offset as isize)
+                as *mut ffi::crypto_generichash_blake2b_state
+        }
+    }
+
+    /**
+     * `new()` initializes a `State` that computes a `Digest` of length
+     * `out_len`, using the optional key `key`.
+     *
+     * `new()` returns `Err(())` for the same invalid parameters as `hash()`.
+     */
+    pub fn new(out_len: usize, key: Option<&[u8]>) -> Result<State, ()> {
+        State::new_salt_personal(out_len, key, None, None)
+    }
+
+    /**
+     * `new_salt_personal()` initializes a `State` like `new()`, but also
+     * takes an optional salt `salt` and an optional personalization string
+     * `personal` into account.
+     */
+    pub fn new_salt_personal(out_len: usize,
+                             key: Option<&[u8]>,
+                             salt: Option<&Salt>,
+                             personal: Option<&Personal>) -> Result<State, ()> {
+        let (key_p, key_len) = try!(check_params(out_len, key));
+        let Salt(ref s) = *salt.unwrap_or(&Salt([0; SALTBYTES]));
+        let Personal(ref p) = *personal.unwrap_or(&Personal([0; PERSONALBYTES]));
+        let (buf, offset) = alloc_state();
+        let mut state = State {
+            buf: buf,
+            offset: offset,
+            out_len: out_len,
+        };
+        unsafe {
+            ffi::crypto_generichash_blake2b_init_salt_personal(state.st(),
+                                                               key_p,
+                                                               key_len,
+                                                               out_len as size_t,
+                                                               s,
+                                                               p);
+        }
+        Ok(state)
+    }
+
+    /**
+     * `update()` feeds the next chunk `data` of the message into the `State`
+     */
+    pub fn update(&mut self, data: &[u8]) {
+        unsafe {
+            ffi::crypto_generichash_blake2b_update(self.st(),
+                                                   data.as_ptr(),
+                                                   data.len() as c_ulonglong);
+        }
+    }
+
+    /**
+     * `finalize()` finishes the computation and returns the `Digest` of all
+     * data passed to `update()`
+     */
+    pub fn finalize(mut self) -> Digest {
+        let mut digest = Digest { len: self.out_len, data: [0; BYTES_MAX] };
+        unsafe {
+            ffi::crypto_generichash_blake2b_final(self.st(),
+                                                  digest.data.as_mut_ptr(),
+                                                  self.out_len as size_t);
+        }
+        digest
+    }
+}
+
+impl Clone for State {
+    fn clone(&self) -> State {
+        let (mut buf, offset) = alloc_state();
+        {
+            let len = ffi::crypto_generichash_blake2b_STATEBYTES;
+            let src = &self.buf[self.offset..self.offset + len];
+            let dst = &mut buf[offset..offset + len];
+            for (d, &s) in dst.iter_mut().zip(src.iter()) {
+                *d = s;
+            }
+        }
+        State { buf: buf, offset: offset, out_len: self.out_len }
+    }
+}
+
+/**
+ * Writing to a `State` feeds the data into the hash computation, so that
+ * `io::copy()` can be used to hash anything that implements `io::Read`.
+ */
+impl Write for State {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Drop for State {
+    fn drop(&mut self) {
+        unsafe {
+            volatile_set_memory(self.buf.as_mut_ptr(), 0, self.buf.len());
+        }
+    }
+}
+
+#[test]
+fn test_vector_abc() {
+    // RFC 7693, Appendix A
+    let h_expected = [0xba,0x80,0xa5,0x3f,0x98,0x1c,0x4d,0x0d
+                     ,0x6a,0x27,0x97,0xb6,0x9f,0x12,0xf6,0xe9
+                     ,0x4c,0x21,0x2f,0x14,0x68,0x5a,0xc4,0xb7
+                     ,0x4b,0x12,0xbb,0x6f,0xdb,0xff,0xa2,0xd1
+                     ,0x7d,0x87,0xc5,0x39,0x2a,0xab,0x79,0x2d
+                     ,0xc2,0x52,0xd5,0xde,0x45,0x33,0xcc,0x95
+                     ,0x18,0xd3,0x8a,0xa8,0xdb,0xf1,0x92,0x5a
+                     ,0xb9,0x23,0x86,0xed,0xd4,0x00,0x99,0x23];
+    let h = hash(b"abc", 64, None).unwrap();
+    assert!(h.len() == 64);
+    assert!(&h[..] == &h_expected[..]);
+}
+
+#[test]
+fn test_vectors() {
+    // the first lines are the keyed KAT of the BLAKE2 reference
+    // implementation, the rest exercise salt, personalization and
+    // different output lengths
+    use self::rustc_serialize::hex::FromHex;
+    use std::old_io::BufferedReader;
+    use std::old_io::File;
+    use std::path::Path;
+
+    let p = &Path::new("testvectors/blake2b.input");
+    let mut r = BufferedReader::new(File::open(p).unwrap());
+    loop {
+        let line = match r.read_line() {
+            Err(_) => break,
+            Ok(line) => line
+        };
+        let mut x = line.split(':');
+        let m = x.next().unwrap().from_hex().unwrap();
+        let k = x.next().unwrap().from_hex().unwrap();
+        let s = x.next().unwrap().from_hex().unwrap();
+        let p = x.next().unwrap().from_hex().unwrap();
+        let h_expected = x.next().unwrap().from_hex().unwrap();
+        let key = if k.len() == 0 { None } else { Some(&k[..]) };
+        let salt = Salt::from_slice(&s);
+        let personal = Personal::from_slice(&p);
+        let h = hash_salt_personal(&m, h_expected.len(), key,
+                                   salt.as_ref(), personal.as_ref()).unwrap();
+        assert!(&h[..] == &h_expected[..]);
+        let mut state = State::new_salt_personal(h_expected.len(), key,
+                                                 salt.as_ref(),
+                                                 personal.as_ref()).unwrap();
+        for chunk in m.chunks(7) {
+            state.update(chunk);
+        }
+        assert!(state.finalize() == h);
+        if salt.is_none() && personal.is_none() {
+            assert!(hash(&m, h_expected.len(), key).unwrap() == h);
+        }
+    }
+}
+
+#[test]
+fn test_invalid_params() {
+    let m = [0u8; 16];
+    let short_key = [0u8; KEYBYTES_MIN - 1];
+    let long_key = [0u8; KEYBYTES_MAX + 1];
+    assert!(hash(&m, BYTES_MIN - 1, None).is_err());
+    assert!(hash(&m, BYTES_MAX + 1, None).is_err());
+    assert!(hash(&m, BYTES, Some(&short_key[..])).is_err());
+    assert!(hash(&m, BYTES, Some(&long_key[..])).is_err());
+    assert!(State::new(BYTES_MAX + 1, None).is_err());
+    assert!(State::new(BYTES, Some(&long_key[..])).is_err());
+    assert!(hash(&m, BYTES_MIN, None).is_ok());
+    assert!(hash(&m, BYTES_MAX, Some(&gen_key()[..])).is_ok());
+}
+
+#[test]
+fn test_key_salt_personal_change_digest() {
+    use randombytes::randombytes;
+    let m = randombytes(100);
+    let k1 = gen_key();
+    let k2 = gen_key();
+    let h = hash(&m, BYTES, Some(&k1[..])).unwrap();
+    assert!(h != hash(&m, BYTES, None).unwrap());
+    assert!(h != hash(&m, BYTES, Some(&k2[..])).unwrap());
+    let s = gen_salt();
+    let p = Personal(*b"sodiumoxide test");
+    let hs = hash_salt_personal(&m, BYTES, Some(&k1[..]), Some(&s), None).unwrap();
+    let hp = hash_salt_personal(&m, BYTES, Some(&k1[..]), None, Some(&p)).unwrap();
+    assert!(h != hs);
+    assert!(h != hp);
+    assert!(hs != hp);
+    assert!(h != hash(&m, BYTES + 1, Some(&k1[..])).unwrap());
+}
+
+#[test]
+fn test_digest_from_slice() {
+    use randombytes::randombytes;
+    let k = gen_key();
+    let m = randombytes(100);
+    let h = hash(&m, BYTES, Some(&k[..])).unwrap();
+    let received = h[..].to_vec();
+    assert!(Digest::from_slice(&received).unwrap() == h);
+    let mut tampered = received.clone();
+    tampered[0] ^= 0x20;
+    assert!(Digest::from_slice(&tampered).unwrap() != h);
+    assert!(Digest::from_slice(&received[..BYTES - 1]).unwrap() != h);
+    assert!(Digest::from_slice(&received[..BYTES_MIN - 1]).is_none());
+    let long = randombytes(BYTES_MAX + 1);
+    assert!(Digest::from_slice(&long).is_none());
+}
+
+#[test]
+fn test_state_alignment() {
+    for _ in (0..16us) {
+        let mut state1 = State::new(BYTES, None).unwrap();
+        assert!(state1.st() as usize % STATEALIGN == 0);
+        let mut state2 = state1.clone();
+        assert!(state2.st() as usize % STATEALIGN == 0);
+    }
+}
+
+#[test]
+fn test_state_clone() {
+    use randombytes::randombytes;
+    let prefix = randombytes(100);
+    let suffix1 = randombytes(200);
+    let suffix2 = randombytes(300);
+    let mut state1 = State::new(BYTES, None).unwrap();
+    state1.update(&prefix);
+    let mut state2 = state1.clone();
+    state1.update(&suffix1);
+    state2.write_all(&suffix2).unwrap();
+    let mut m1 = prefix.clone();
+    m1.push_all(&suffix1);
+    let mut m2 = prefix.clone();
+    m2.push_all(&suffix2);
+    assert!(state1.finalize() == hash(&m1, BYTES, None).unwrap());
+    assert!(state2.finalize() == hash(&m2, BYTES, None).unwrap());
+}
+
+#[cfg(test)]
+mod bench {
+    extern crate test;
+    use randombytes::randombytes;
+    use super::*;
+
+    const BENCH_SIZES: [usize; 14] = [0, 1, 2, 4, 8, 16, 32, 64,
+                                      128, 256, 512, 1024, 2048, 4096];
+
+    #[bench]
+    fn bench_hash(b: &mut test::Bencher) {
+        let ms: Vec<Vec<u8>> = BENCH_SIZES.iter().map(|s| {
+            randombytes(*s)
+        }).collect();
+        b.iter(|| {
+            for m in ms.iter() {
+                hash(&m, BYTES, None).unwrap();
+            }
+        });
+    }
+}
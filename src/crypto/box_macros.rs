@@ -0,0 +1,735 @@
+macro_rules! box_module (($keypair_name:ident,
+                          $beforenm_name:ident,
+                          $seal_name:ident,
+                          $open_name:ident,
+                          $seal_afternm_name:ident,
+                          $open_afternm_name:ident,
+                          $seal_detached_name:ident,
+                          $open_detached_name:ident,
+                          $seal_detached_afternm_name:ident,
+                          $open_detached_afternm_name:ident,
+                          $publickeybytes:expr,
+                          $secretkeybytes:expr,
+                          $noncebytes:expr,
+                          $precomputedkeybytes:expr,
+                          $macbytes:expr) => (
+
+use libc::c_ulonglong;
+use std::intrinsics::volatile_set_memory;
+use std::ops::{Index, Range, RangeFrom, RangeFull, RangeTo};
+use std::iter::repeat;
+use randombytes::randombytes_into;
+use crypto::verify::verify_16;
+
+pub const PUBLICKEYBYTES: usize = $publickeybytes;
+pub const SECRETKEYBYTES: usize = $secretkeybytes;
+pub const NONCEBYTES: usize = $noncebytes;
+pub const PRECOMPUTEDKEYBYTES: usize = $precomputedkeybytes;
+pub const MACBYTES: usize = $macbytes;
+
+/**
+ * `PublicKey` for asymmetric authenticated encryption
+ */
+#[derive(Copy)]
+pub struct PublicKey(pub [u8; PUBLICKEYBYTES]);
+
+newtype_clone!(PublicKey);
+newtype_impl!(PublicKey, PUBLICKEYBYTES);
+
+/**
+ * `SecretKey` for asymmetric authenticated encryption
+ *
+ * When a `SecretKey` goes out of scope its contents
+ * will be zeroed out
+ */
+pub struct SecretKey(pub [u8; SECRETKEYBYTES]);
+
+newtype_drop!(SecretKey);
+newtype_clone!(SecretKey);
+newtype_impl!(SecretKey, SECRETKEYBYTES);
+
+/**
+ * `Nonce` for asymmetric authenticated encryption
+ */
+#[derive(Copy)]
+pub struct Nonce(pub [u8; NONCEBYTES]);
+
+newtype_clone!(Nonce);
+newtype_impl!(Nonce, NONCEBYTES);
+
+/**
+ * Authentication `Tag` for the detached mode
+ *
+ * The tag implements the traits `PartialEq` and `Eq` using constant-time
+ * comparison functions. See `sodiumoxide::crypto::verify::verify_16`
+ */
+#[derive(Copy)]
+pub struct Tag(pub [u8; MACBYTES]);
+
+impl Eq for Tag {}
+
+impl PartialEq for Tag {
+    fn eq(&self, &Tag(other): &Tag) -> bool {
+        let &Tag(ref tag) = self;
+        verify_16(tag, &other)
+    }
+}
+
+newtype_clone!(Tag);
+newtype_impl!(Tag, MACBYTES);
+
+/**
+ * `gen_keypair()` randomly generates a secret key and a corresponding public key.
+ *
+ * THREAD SAFETY: `gen_keypair()` is thread-safe provided that you have
+ * called `sodiumoxide::init()` once before using any other function
+ * from sodiumoxide.
+ */
+pub fn gen_keypair() -> (PublicKey, SecretKey) {
+    unsafe {
+        let mut pk = [0u8; PUBLICKEYBYTES];
+        let mut sk = [0u8; SECRETKEYBYTES];
+        $keypair_name(
+            &mut pk,
+            &mut sk);
+        (PublicKey(pk), SecretKey(sk))
+    }
+}
+
+/**
+ * `gen_nonce()` randomly generates a nonce
+ *
+ * THREAD SAFETY: `gen_nonce()` is thread-safe provided that you have
+ * called `sodiumoxide::init()` once before using any other function
+ * from sodiumoxide.
+ */
+pub fn gen_nonce() -> Nonce {
+    let mut n = [0; NONCEBYTES];
+    randombytes_into(&mut n);
+    Nonce(n)
+}
+
+/**
+ * `seal()` encrypts and authenticates a message `m` using the senders secret key `sk`,
+ * the receivers public key `pk` and a nonce `n`. It returns a ciphertext `c`.
+ */
+pub fn seal(m: &[u8],
+            &Nonce(ref n): &Nonce,
+            &PublicKey(ref pk): &PublicKey,
+            &SecretKey(ref sk): &SecretKey) -> Vec<u8> {
+    let mut c: Vec<u8> = repeat(0u8).take(m.len() + MACBYTES).collect();
+    unsafe {
+        $seal_name(c.as_mut_ptr(),
+                   m.as_ptr(),
+                   m.len() as c_ulonglong,
+                   n,
+                   pk,
+                   sk);
+    }
+    c
+}
+
+/**
+ * `open()` verifies and decrypts a ciphertext `c` using the receiver's secret key `sk`,
+ * the senders public key `pk`, and a nonce `n`. It returns a plaintext `Some(m)`.
+ * If the ciphertext fails verification, `open()` returns `None`.
+ */
+pub fn open(c: &[u8],
+            &Nonce(ref n): &Nonce,
+            &PublicKey(ref pk): &PublicKey,
+            &SecretKey(ref sk): &SecretKey) -> Option<Vec<u8>> {
+    if c.len() < MACBYTES {
+        return None
+    }
+    let mut m: Vec<u8> = repeat(0u8).take(c.len() - MACBYTES).collect();
+    let ret = unsafe {
+        $open_name(m.as_mut_ptr(),
+                   c.as_ptr(),
+                   c.len() as c_ulonglong,
+                   n,
+                   pk,
+                   sk)
+    };
+    if ret == 0 {
+        Some(m)
+    } else {
+        None
+    }
+}
+
+/**
+ * `seal_inplace()` encrypts and authenticates the message in `buf` using the senders
+ * secret key `sk`, the receivers public key `pk` and a nonce `n`. After
+ * `seal_inplace()` returns `buf` contains the ciphertext, which is `MACBYTES` longer
+ * than the message. No new buffer is allocated if `buf` has enough spare capacity.
+ */
+pub fn seal_inplace(buf: &mut Vec<u8>,
+                    &Nonce(ref n): &Nonce,
+                    &PublicKey(ref pk): &PublicKey,
+                    &SecretKey(ref sk): &SecretKey) {
+    let mlen = buf.len();
+    buf.extend(repeat(0u8).take(MACBYTES));
+    unsafe {
+        $seal_name(buf.as_mut_ptr(),
+                   buf.as_ptr(),
+                   mlen as c_ulonglong,
+                   n,
+                   pk,
+                   sk);
+    }
+}
+
+/**
+ * `open_inplace()` verifies and decrypts the ciphertext in `buf` using the receiver's
+ * secret key `sk`, the senders public key `pk`, and a nonce `n`. If `open_inplace()`
+ * returns `Ok(())` `buf` contains the plaintext.
+ * If the ciphertext fails verification, `open_inplace()` returns `Err(())` and leaves
+ * `buf` unchanged.
+ */
+pub fn open_inplace(buf: &mut Vec<u8>,
+                    &Nonce(ref n): &Nonce,
+                    &PublicKey(ref pk): &PublicKey,
+                    &SecretKey(ref sk): &SecretKey) -> Result<(), ()> {
+    let clen = buf.len();
+    if clen < MACBYTES {
+        return Err(())
+    }
+    let ret = unsafe {
+        $open_name(buf.as_mut_ptr(),
+                   buf.as_ptr(),
+                   clen as c_ulonglong,
+                   n,
+                   pk,
+                   sk)
+    };
+    if ret == 0 {
+        buf.truncate(clen - MACBYTES);
+        Ok(())
+    } else {
+        Err(())
+    }
+}
+
+/**
+ * `seal_detached()` encrypts and authenticates a message `m` using the senders secret key `sk`,
+ * the receivers public key `pk` and a nonce `n`. `m` is encrypted in place, so after
+ * `seal_detached()` returns it will contain the ciphertext. The authentication tag is
+ * returned separately.
+ */
+pub fn seal_detached(m: &mut [u8],
+                     &Nonce(ref n): &Nonce,
+                     &PublicKey(ref pk): &PublicKey,
+                     &SecretKey(ref sk): &SecretKey) -> Tag {
+    let mut tag = [0u8; MACBYTES];
+    unsafe {
+        $seal_detached_name(m.as_mut_ptr(),
+                            &mut tag,
+                            m.as_ptr(),
+                            m.len() as c_ulonglong,
+                            n,
+                            pk,
+                            sk);
+    }
+    Tag(tag)
+}
+
+/**
+ * `open_detached()` verifies and decrypts a ciphertext `c` using the receiver's secret key `sk`,
+ * the senders public key `pk`, a nonce `n` and the authentication tag `tag`. `c` is decrypted
+ * in place, so if `open_detached()` returns `Ok(())` it will contain the plaintext.
+ * If the ciphertext fails verification, `open_detached()` returns `Err(())` and leaves `c`
+ * unchanged.
+ */
+pub fn open_detached(c: &mut [u8],
+                     &Tag(ref tag): &Tag,
+                     &Nonce(ref n): &Nonce,
+                     &PublicKey(ref pk): &PublicKey,
+                     &SecretKey(ref sk): &SecretKey) -> Result<(), ()> {
+    let ret = unsafe {
+        $open_detached_name(c.as_mut_ptr(),
+                            c.as_ptr(),
+                            tag,
+                            c.len() as c_ulonglong,
+                            n,
+                            pk,
+                            sk)
+    };
+    if ret == 0 {
+        Ok(())
+    } else {
+        Err(())
+    }
+}
+
+/**
+ * Applications that send several messages to the same receiver can gain speed by
+ * splitting `seal()` into two steps, `precompute()` and `seal_precomputed()`.
+ * Similarly, applications that receive several messages from the same sender can gain
+ * speed by splitting `open()` into two steps, `precompute()` and `open_precomputed()`.
+ *
+ * When a `PrecomputedKey` goes out of scope its contents will be zeroed out
+ */
+pub struct PrecomputedKey([u8; PRECOMPUTEDKEYBYTES]);
+
+newtype_drop!(PrecomputedKey);
+newtype_clone!(PrecomputedKey);
+newtype_impl!(PrecomputedKey, PRECOMPUTEDKEYBYTES);
+
+/**
+ * `precompute()` computes an intermediate key that can be used by `seal_precomputed()`
+ * and `open_precomputed()`
+ */
+pub fn precompute(&PublicKey(ref pk): &PublicKey,
+                  &SecretKey(ref sk): &SecretKey) -> PrecomputedKey {
+    let mut k = [0u8; PRECOMPUTEDKEYBYTES];
+    unsafe {
+        $beforenm_name(&mut k,
+                       pk,
+                       sk);
+    }
+    PrecomputedKey(k)
+}
+
+/**
+ * `seal_precomputed()` encrypts and authenticates a message `m` using a precomputed key `k`,
+ * and a nonce `n`. It returns a ciphertext `c`.
+ */
+pub fn seal_precomputed(m: &[u8],
+                        &Nonce(ref n): &Nonce,
+                        &PrecomputedKey(ref k): &PrecomputedKey) -> Vec<u8> {
+    let mut c: Vec<u8> = repeat(0u8).take(m.len() + MACBYTES).collect();
+    unsafe {
+        $seal_afternm_name(c.as_mut_ptr(),
+                           m.as_ptr(),
+                           m.len() as c_ulonglong,
+                           n,
+                           k);
+    }
+    c
+}
+
+/**
+ * `open_precomputed()` verifies and decrypts a ciphertext `c` using a precomputed
+ * key `k` and a nonce `n`. It returns a plaintext `Some(m)`.
+ * If the ciphertext fails verification, `open_precomputed()` returns `None`.
+ */
+pub fn open_precomputed(c: &[u8],
+                        &Nonce(ref n): &Nonce,
+                        &PrecomputedKey(ref k): &PrecomputedKey) -> Option<Vec<u8>> {
+    if c.len() < MACBYTES {
+        return None
+    }
+    let mut m: Vec<u8> = repeat(0u8).take(c.len() - MACBYTES).collect();
+    let ret = unsafe {
+        $open_afternm_name(m.as_mut_ptr(),
+                           c.as_ptr(),
+                           c.len() as c_ulonglong,
+                           n,
+                           k)
+    };
+    if ret == 0 {
+        Some(m)
+    } else {
+        None
+    }
+}
+
+/**
+ * `seal_precomputed_inplace()` encrypts and authenticates the message in `buf` using
+ * a precomputed key `k` and a nonce `n`. After `seal_precomputed_inplace()` returns
+ * `buf` contains the ciphertext, which is `MACBYTES` longer than the message.
+ * No new buffer is allocated if `buf` has enough spare capacity.
+ */
+pub fn seal_precomputed_inplace(buf: &mut Vec<u8>,
+                                &Nonce(ref n): &Nonce,
+                                &PrecomputedKey(ref k): &PrecomputedKey) {
+    let mlen = buf.len();
+    buf.extend(repeat(0u8).take(MACBYTES));
+    unsafe {
+        $seal_afternm_name(buf.as_mut_ptr(),
+                           buf.as_ptr(),
+                           mlen as c_ulonglong,
+                           n,
+                           k);
+    }
+}
+
+/**
+ * `open_precomputed_inplace()` verifies and decrypts the ciphertext in `buf` using a
+ * precomputed key `k` and a nonce `n`. If `open_precomputed_inplace()` returns
+ * `Ok(())` `buf` contains the plaintext.
+ * If the ciphertext fails verification, `open_precomputed_inplace()` returns
+ * `Err(())` and leaves `buf` unchanged.
+ */
+pub fn open_precomputed_inplace(buf: &mut Vec<u8>,
+                                &Nonce(ref n): &Nonce,
+                                &PrecomputedKey(ref k): &PrecomputedKey) -> Result<(), ()> {
+    let clen = buf.len();
+    if clen < MACBYTES {
+        return Err(())
+    }
+    let ret = unsafe {
+        $open_afternm_name(buf.as_mut_ptr(),
+                           buf.as_ptr(),
+                           clen as c_ulonglong,
+                           n,
+                           k)
+    };
+    if ret == 0 {
+        buf.truncate(clen - MACBYTES);
+        Ok(())
+    } else {
+        Err(())
+    }
+}
+
+/**
+ * `seal_detached_precomputed()` encrypts and authenticates a message `m` using a
+ * precomputed key `k` and a nonce `n`. `m` is encrypted in place, so after
+ * `seal_detached_precomputed()` returns it will contain the ciphertext. The
+ * authentication tag is returned separately.
+ */
+pub fn seal_detached_precomputed(m: &mut [u8],
+                                 &Nonce(ref n): &Nonce,
+                                 &PrecomputedKey(ref k): &PrecomputedKey) -> Tag {
+    let mut tag = [0u8; MACBYTES];
+    unsafe {
+        $seal_detached_afternm_name(m.as_mut_ptr(),
+                                    &mut tag,
+                                    m.as_ptr(),
+                                    m.len() as c_ulonglong,
+                                    n,
+                                    k);
+    }
+    Tag(tag)
+}
+
+/**
+ * `open_detached_precomputed()` verifies and decrypts a ciphertext `c` using a
+ * precomputed key `k`, a nonce `n` and the authentication tag `tag`. `c` is
+ * decrypted in place, so if `open_detached_precomputed()` returns `Ok(())` it
+ * will contain the plaintext.
+ * If the ciphertext fails verification, `open_detached_precomputed()` returns
+ * `Err(())` and leaves `c` unchanged.
+ */
+pub fn open_detached_precomputed(c: &mut [u8],
+                                 &Tag(ref tag): &Tag,
+                                 &Nonce(ref n): &Nonce,
+                                 &PrecomputedKey(ref k): &PrecomputedKey) -> Result<(), ()> {
+    let ret = unsafe {
+        $open_detached_afternm_name(c.as_mut_ptr(),
+                                    c.as_ptr(),
+                                    tag,
+                                    c.len() as c_ulonglong,
+                                    n,
+                                    k)
+    };
+    if ret == 0 {
+        Ok(())
+    } else {
+        Err(())
+    }
+}
+
+#[test]
+fn test_seal_open() {
+    use randombytes::randombytes;
+    for i in (0..256us) {
+        let (pk1, sk1) = gen_keypair();
+        let (pk2, sk2) = gen_keypair();
+        let m = randombytes(i);
+        let n = gen_nonce();
+        let c = seal(&m, &n, &pk1, &sk2);
+        let opened = open(&c, &n, &pk2, &sk1);
+        assert!(Some(m) == opened);
+    }
+}
+
+#[test]
+fn test_seal_open_precomputed() {
+    use randombytes::randombytes;
+    for i in (0..256us) {
+        let (pk1, sk1) = gen_keypair();
+        let (pk2, sk2) = gen_keypair();
+        let k1 = precompute(&pk1, &sk2);
+        let PrecomputedKey(k1buf) = k1;
+        let k2 = precompute(&pk2, &sk1);
+        let PrecomputedKey(k2buf) = k2;
+        assert!(k1buf == k2buf);
+        let m = randombytes(i);
+        let n = gen_nonce();
+        let c = seal_precomputed(&m, &n, &k1);
+        let opened = open_precomputed(&c, &n, &k2);
+        assert!(Some(m) == opened);
+    }
+}
+
+#[test]
+fn test_seal_open_tamper() {
+    use randombytes::randombytes;
+    for i in (0..32us) {
+        let (pk1, sk1) = gen_keypair();
+        let (pk2, sk2) = gen_keypair();
+        let m = randombytes(i);
+        let n = gen_nonce();
+        let mut cv = seal(&m, &n, &pk1, &sk2);
+        let c = cv.as_mut_slice();
+        for j in (0..c.len()) {
+            c[j] ^= 0x20;
+            assert!(None == open(c, &n, &pk2, &sk1));
+            c[j] ^= 0x20;
+        }
+    }
+}
+
+#[test]
+fn test_seal_open_precomputed_tamper() {
+    use randombytes::randombytes;
+    for i in (0..32us) {
+        let (pk1, sk1) = gen_keypair();
+        let (pk2, sk2) = gen_keypair();
+        let k1 = precompute(&pk1, &sk2);
+        let k2 = precompute(&pk2, &sk1);
+        let m = randombytes(i);
+        let n = gen_nonce();
+        let mut cv = seal_precomputed(&m, &n, &k1);
+        let c = cv.as_mut_slice();
+        for j in (0..c.len()) {
+            c[j] ^= 0x20;
+            assert!(None == open_precomputed(c, &n, &k2));
+            c[j] ^= 0x20;
+        }
+    }
+}
+
+#[test]
+fn test_seal_open_inplace() {
+    use randombytes::randombytes;
+    for i in (0..256us) {
+        let (pk1, sk1) = gen_keypair();
+        let (pk2, sk2) = gen_keypair();
+        let k1 = precompute(&pk1, &sk2);
+        let k2 = precompute(&pk2, &sk1);
+        let m = randombytes(i);
+        let n = gen_nonce();
+        let mut buf = m.clone();
+        seal_inplace(&mut buf, &n, &pk1, &sk2);
+        assert!(buf == seal(&m, &n, &pk1, &sk2));
+        assert!(open_inplace(&mut buf, &n, &pk2, &sk1).is_ok());
+        assert!(buf == m);
+        seal_precomputed_inplace(&mut buf, &n, &k1);
+        assert!(buf == seal(&m, &n, &pk1, &sk2));
+        assert!(open_precomputed_inplace(&mut buf, &n, &k2).is_ok());
+        assert!(buf == m);
+    }
+}
+
+#[test]
+fn test_seal_open_inplace_tamper() {
+    use randombytes::randombytes;
+    for i in (0..32us) {
+        let (pk1, sk1) = gen_keypair();
+        let (pk2, sk2) = gen_keypair();
+        let k2 = precompute(&pk2, &sk1);
+        let n = gen_nonce();
+        let mut c = randombytes(i);
+        seal_inplace(&mut c, &n, &pk1, &sk2);
+        for j in (0..c.len()) {
+            let mut tampered = c.clone();
+            tampered[j] ^= 0x20;
+            let expected = tampered.clone();
+            assert!(open_inplace(&mut tampered, &n, &pk2, &sk1).is_err());
+            assert!(open_precomputed_inplace(&mut tampered, &n, &k2).is_err());
+            assert!(tampered == expected);
+        }
+    }
+}
+
+#[test]
+fn test_seal_open_detached() {
+    use randombytes::randombytes;
+    for i in (0..256us) {
+        let (pk1, sk1) = gen_keypair();
+        let (pk2, sk2) = gen_keypair();
+        let m = randombytes(i);
+        let n = gen_nonce();
+        let mut buf = m.clone();
+        let tag = seal_detached(buf.as_mut_slice(), &n, &pk1, &sk2);
+        assert!(open_detached(buf.as_mut_slice(), &tag, &n, &pk2, &sk1).is_ok());
+        assert!(m == buf);
+    }
+}
+
+#[test]
+fn test_seal_open_detached_precomputed() {
+    use randombytes::randombytes;
+    for i in (0..256us) {
+        let (pk1, sk1) = gen_keypair();
+        let (pk2, sk2) = gen_keypair();
+        let k1 = precompute(&pk1, &sk2);
+        let k2 = precompute(&pk2, &sk1);
+        let m = randombytes(i);
+        let n = gen_nonce();
+        let mut buf = m.clone();
+        let tag = seal_detached_precomputed(buf.as_mut_slice(), &n, &k1);
+        assert!(open_detached_precomputed(buf.as_mut_slice(), &tag, &n, &k2).is_ok());
+        assert!(m == buf);
+    }
+}
+
+#[test]
+fn test_seal_detached_same() {
+    use randombytes::randombytes;
+    for i in (0..256us) {
+        let (pk1, _) = gen_keypair();
+        let (_, sk2) = gen_keypair();
+        let k = precompute(&pk1, &sk2);
+        let m = randombytes(i);
+        let n = gen_nonce();
+        let c = seal(&m, &n, &pk1, &sk2);
+        let mut buf = m.clone();
+        let Tag(tag) = seal_detached(buf.as_mut_slice(), &n, &pk1, &sk2);
+        assert!(&c[..MACBYTES] == &tag[..]);
+        assert!(&c[MACBYTES..] == &buf[..]);
+        let mut bufpre = m.clone();
+        let Tag(tagpre) = seal_detached_precomputed(bufpre.as_mut_slice(), &n, &k);
+        assert!(tag == tagpre);
+        assert!(buf == bufpre);
+    }
+}
+
+#[test]
+fn test_seal_open_detached_tamper() {
+    use randombytes::randombytes;
+    for i in (0..32us) {
+        let (pk1, sk1) = gen_keypair();
+        let (pk2, sk2) = gen_keypair();
+        let k2 = precompute(&pk2, &sk1);
+        let n = gen_nonce();
+        let mut c = randombytes(i);
+        let Tag(mut tagbuf) = seal_detached(c.as_mut_slice(), &n, &pk1, &sk2);
+        for j in (0..c.len()) {
+            let mut tampered = c.clone();
+            tampered[j] ^= 0x20;
+            assert!(open_detached(tampered.as_mut_slice(), &Tag(tagbuf),
+                                  &n, &pk2, &sk1).is_err());
+            assert!(open_detached_precomputed(tampered.as_mut_slice(), &Tag(tagbuf),
+                                              &n, &k2).is_err());
+        }
+        for j in (0..tagbuf.len()) {
+            let mut cv = c.clone();
+            tagbuf[j] ^= 0x20;
+            assert!(open_detached(cv.as_mut_slice(), &Tag(tagbuf),
+                                  &n, &pk2, &sk1).is_err());
+            assert!(open_detached_precomputed(cv.as_mut_slice(), &Tag(tagbuf),
+                                              &n, &k2).is_err());
+            assert!(cv == c);
+            tagbuf[j] ^= 0x20;
+        }
+    }
+}
+
+#[cfg(test)]
+mod bench {
+    extern crate test;
+    use randombytes::randombytes;
+    use super::*;
+
+    const BENCH_SIZES: [usize; 14] = [0, 1, 2, 4, 8, 16, 32, 64,
+                                      128, 256, 512, 1024, 2048, 4096];
+
+    #[bench]
+    fn bench_seal_open(b: &mut test::Bencher) {
+        let (pk, sk) = gen_keypair();
+        let n = gen_nonce();
+        let ms: Vec<Vec<u8>> = BENCH_SIZES.iter().map(|s| {
+            randombytes(*s)
+        }).collect();
+        b.iter(|| {
+            for m in ms.iter() {
+                open(&seal(m, &n, &pk, &sk), &n, &pk, &sk).unwrap();
+            }
+        });
+    }
+
+    #[bench]
+    fn bench_seal_open_inplace(b: &mut test::Bencher) {
+        let (pk, sk) = gen_keypair();
+        let n = gen_nonce();
+        let mut ms: Vec<Vec<u8>> = BENCH_SIZES.iter().map(|s| {
+            let mut m = Vec::with_capacity(*s + MACBYTES);
+            m.push_all(&randombytes(*s));
+            m
+        }).collect();
+        b.iter(|| {
+            for m in ms.iter_mut() {
+                seal_inplace(m, &n, &pk, &sk);
+                open_inplace(m, &n, &pk, &sk).unwrap();
+            }
+        });
+    }
+
+    #[bench]
+    fn bench_seal_open_precomputed(b: &mut test::Bencher) {
+        let (pk, sk) = gen_keypair();
+        let k = precompute(&pk, &sk);
+        let n = gen_nonce();
+        let ms: Vec<Vec<u8>> = BENCH_SIZES.iter().map(|s| {
+            randombytes(*s)
+        }).collect();
+        b.iter(|| {
+            for m in ms.iter() {
+                open_precomputed(&seal_precomputed(m, &n, &k), &n, &k).unwrap();
+            }
+        });
+    }
+
+    #[bench]
+    fn bench_seal_open_precomputed_inplace(b: &mut test::Bencher) {
+        let (pk, sk) = gen_keypair();
+        let k = precompute(&pk, &sk);
+        let n = gen_nonce();
+        let mut ms: Vec<Vec<u8>> = BENCH_SIZES.iter().map(|s| {
+            let mut m = Vec::with_capacity(*s + MACBYTES);
+            m.push_all(&randombytes(*s));
+            m
+        }).collect();
+        b.iter(|| {
+            for m in ms.iter_mut() {
+                seal_precomputed_inplace(m, &n, &k);
+                open_precomputed_inplace(m, &n, &k).unwrap();
+            }
+        });
+    }
+
+    #[bench]
+    fn bench_seal_open_detached_precomputed(b: &mut test::Bencher) {
+        let (pk, sk) = gen_keypair();
+        let k = precompute(&pk, &sk);
+        let n = gen_nonce();
+        let mut ms: Vec<Vec<u8>> = BENCH_SIZES.iter().map(|s| {
+            randombytes(*s)
+        }).collect();
+        b.iter(|| {
+            for m in ms.iter_mut() {
+                let tag = seal_detached_precomputed(m.as_mut_slice(), &n, &k);
+                open_detached_precomputed(m.as_mut_slice(), &tag, &n, &k).unwrap();
+            }
+        });
+    }
+
+    #[bench]
+    fn bench_precompute(b: &mut test::Bencher) {
+        let (pk, sk) = gen_keypair();
+        b.iter(|| {
+            /* we do this benchmark as many times as the other benchmarks
+            so that we can compare the times */
+            for _ in BENCH_SIZES.iter() {
+                precompute(&pk, &sk);
+                precompute(&pk, &sk);
+            }
+        });
+    }
+}
+
+));
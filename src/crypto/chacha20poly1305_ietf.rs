@@ -0,0 +1,53 @@
+/*!
+`crypto_aead_chacha20poly1305_ietf`, the IETF variant of the ChaCha20-Poly1305
+construction with a 96-bit nonce, as specified in
+[RFC 8439](https://tools.ietf.org/html/rfc8439).
+
+This is the variant used by TLS, QUIC and WireGuard.
+*/
+#[cfg(test)]
+extern crate "rustc-serialize" as rustc_serialize;
+use ffi::{crypto_aead_chacha20poly1305_ietf_encrypt,
+          crypto_aead_chacha20poly1305_ietf_decrypt,
+          crypto_aead_chacha20poly1305_ietf_encrypt_detached,
+          crypto_aead_chacha20poly1305_ietf_decrypt_detached,
+          crypto_aead_chacha20poly1305_ietf_KEYBYTES,
+          crypto_aead_chacha20poly1305_ietf_NPUBBYTES,
+          crypto_aead_chacha20poly1305_ietf_ABYTES};
+
+aead_module!(crypto_aead_chacha20poly1305_ietf_encrypt,
+             crypto_aead_chacha20poly1305_ietf_decrypt,
+             crypto_aead_chacha20poly1305_ietf_encrypt_detached,
+             crypto_aead_chacha20poly1305_ietf_decrypt_detached,
+             crypto_aead_chacha20poly1305_ietf_KEYBYTES,
+             crypto_aead_chacha20poly1305_ietf_NPUBBYTES,
+             crypto_aead_chacha20poly1305_ietf_ABYTES,
+             true);
+
+#[test]
+fn test_vectors_rfc8439() {
+    // test vectors from RFC 8439, Section 2.8.2 and Appendix A.5
+    use self::rustc_serialize::hex::FromHex;
+    use std::old_io::BufferedReader;
+    use std::old_io::File;
+    use std::path::Path;
+
+    let p = &Path::new("testvectors/chacha20poly1305_ietf.input");
+    let mut r = BufferedReader::new(File::open(p).unwrap());
+    loop {
+        let line = match r.read_line() {
+            Err(_) => break,
+            Ok(line) => line
+        };
+        let mut x = line.split(':');
+        let k = Key::from_slice(&x.next().unwrap().from_hex().unwrap()).unwrap();
+        let n = Nonce::from_slice(&x.next().unwrap().from_hex().unwrap()).unwrap();
+        let ad = x.next().unwrap().from_hex().unwrap();
+        let m = x.next().unwrap().from_hex().unwrap();
+        let c_expected = x.next().unwrap().from_hex().unwrap();
+        let c = seal(&m, Some(&ad[..]), &n, &k);
+        assert!(c == c_expected);
+        let m2 = open(&c, Some(&ad[..]), &n, &k);
+        assert!(Some(m) == m2);
+    }
+}
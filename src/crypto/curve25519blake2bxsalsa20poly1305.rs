@@ -0,0 +1,157 @@
+/*!
+`crypto_box_seal`, anonymous encryption to a Curve25519 public key using an
+ephemeral key pair, a nonce derived with BLAKE2b and
+`crypto_box_curve25519xsalsa20poly1305`.
+*/
+use ffi;
+use libc::c_ulonglong;
+use std::iter::repeat;
+pub use crypto::asymmetricbox::curve25519xsalsa20poly1305::{PublicKey,
+                                                            SecretKey,
+                                                            gen_keypair};
+
+pub const SEALBYTES: usize = ffi::crypto_box_SEALBYTES;
+
+/**
+ * `seal()` encrypts a message `m` for the recipient whose public key is `pk`.
+ * It returns the ciphertext `c`, which is `SEALBYTES` longer than `m`.
+ *
+ * The sender is anonymous: the ephemeral key pair used to encrypt `m` is
+ * discarded, so not even the sender can decrypt `c`.
+ *
+ * THREAD SAFETY: `seal()` is thread-safe provided that you have
+ * called `sodiumoxide::init()` once before using any other function
+ * from sodiumoxide.
+ */
+pub fn seal(m: &[u8],
+            &PublicKey(ref pk): &PublicKey) -> Vec<u8> {
+    let mut c: Vec<u8> = repeat(0u8).take(m.len() + SEALBYTES).collect();
+    unsafe {
+        ffi::crypto_box_seal(c.as_mut_ptr(),
+                             m.as_ptr(),
+                             m.len() as c_ulonglong,
+                             pk);
+    }
+    c
+}
+
+/**
+ * `open()` verifies and decrypts a ciphertext `c` using the recipient's
+ * public key `pk` and secret key `sk`. It returns a plaintext `Some(m)`.
+ * If the ciphertext fails verification, `open()` returns `None`.
+ */
+pub fn open(c: &[u8],
+            &PublicKey(ref pk): &PublicKey,
+            &SecretKey(ref sk): &SecretKey) -> Option<Vec<u8>> {
+    if c.len() < SEALBYTES {
+        return None
+    }
+    let mut m: Vec<u8> = repeat(0u8).take(c.len() - SEALBYTES).collect();
+    let ret = unsafe {
+        ffi::crypto_box_seal_open(m.as_mut_ptr(),
+                                  c.as_ptr(),
+                                  c.len() as c_ulonglong,
+                                  pk,
+                                  sk)
+    };
+    if ret == 0 {
+        Some(m)
+    } else {
+        None
+    }
+}
+
+#[test]
+fn test_seal_open() {
+    use randombytes::randombytes;
+    for i in (0..256us) {
+        let (pk, sk) = gen_keypair();
+        let m = randombytes(i);
+        let c = seal(&m, &pk);
+        assert!(c.len() == m.len() + SEALBYTES);
+        let opened = open(&c, &pk, &sk);
+        assert!(Some(m) == opened);
+    }
+}
+
+#[test]
+fn test_seal_randomized() {
+    use randombytes::randombytes;
+    let (pk, _) = gen_keypair();
+    let m = randombytes(32);
+    assert!(seal(&m, &pk) != seal(&m, &pk));
+}
+
+#[test]
+fn test_seal_open_tamper() {
+    use randombytes::randombytes;
+    for i in (0..32us) {
+        let (pk, sk) = gen_keypair();
+        let m = randombytes(i);
+        let mut cv = seal(&m, &pk);
+        let c = cv.as_mut_slice();
+        for j in (0..c.len()) {
+            c[j] ^= 0x20;
+            assert!(None == open(c, &pk, &sk));
+            c[j] ^= 0x20;
+        }
+    }
+}
+
+#[test]
+fn test_open_wrong_key() {
+    use randombytes::randombytes;
+    let (pk1, _) = gen_keypair();
+    let (pk2, sk2) = gen_keypair();
+    let m = randombytes(32);
+    let c = seal(&m, &pk1);
+    assert!(None == open(&c, &pk2, &sk2));
+    assert!(None == open(&c[..SEALBYTES - 1], &pk1, &sk2));
+}
+
+#[test]
+fn test_vector_1() {
+    // the recipient is bob from tests/box2.c in NaCl
+    let bobsk = SecretKey([0x5d,0xab,0x08,0x7e,0x62,0x4a,0x8a,0x4b,
+                           0x79,0xe1,0x7f,0x8b,0x83,0x80,0x0e,0xe6,
+                           0x6f,0x3b,0xb1,0x29,0x26,0x18,0xb6,0xfd,
+                           0x1c,0x2f,0x8b,0x27,0xff,0x88,0xe0,0xeb]);
+    let bobpk = PublicKey([0xde,0x9e,0xdb,0x7d,0x7b,0x7d,0xc1,0xb4,
+                           0xd3,0x5b,0x61,0xc2,0xec,0xe4,0x35,0x37,
+                           0x3f,0x83,0x43,0xc8,0x5b,0x78,0x67,0x4d,
+                           0xad,0xfc,0x7e,0x14,0x6f,0x88,0x2b,0x4f]);
+    let c = [0x82,0x81,0x2c,0xf1,0x27,0xc5,0x9c,0xba,
+             0x39,0x61,0x73,0xa6,0x2b,0xeb,0xae,0x27,
+             0x69,0x6f,0xc1,0xa4,0x41,0x91,0x32,0xdc,
+             0xd1,0x25,0x8a,0x59,0x58,0x25,0xb2,0x6d,
+             0x42,0xaa,0xce,0xa4,0x00,0x1a,0xcf,0xa5,
+             0x38,0xb7,0xd7,0x41,0x2e,0xda,0x86,0x9d,
+             0xf5,0x71,0xe1,0x8d,0x72,0x2f,0xcd,0xc6,
+             0x2a,0x44,0x6f,0x49,0x6a,0x46,0x05,0x80,
+             0xab,0x20,0xe5,0x64,0xe6,0xa2];
+    let m = open(&c, &bobpk, &bobsk);
+    assert!(m == Some(b"sealed box test vector".to_vec()));
+}
+
+#[cfg(test)]
+mod bench {
+    extern crate test;
+    use randombytes::randombytes;
+    use super::*;
+
+    const BENCH_SIZES: [usize; 14] = [0, 1, 2, 4, 8, 16, 32, 64,
+                                      128, 256, 512, 1024, 2048, 4096];
+
+    #[bench]
+    fn bench_seal_open(b: &mut test::Bencher) {
+        let (pk, sk) = gen_keypair();
+        let ms: Vec<Vec<u8>> = BENCH_SIZES.iter().map(|s| {
+            randombytes(*s)
+        }).collect();
+        b.iter(|| {
+            for m in ms.iter() {
+                open(&seal(&m, &pk), &pk, &sk).unwrap();
+            }
+        });
+    }
+}
@@ -0,0 +1,155 @@
+/*!
+`crypto_box_curve25519xchacha20poly1305`, a particular
+combination of Curve25519, XChaCha20, and Poly1305 which works like
+`crypto_box_curve25519xsalsa20poly1305` with the Salsa20 stream cipher
+replaced by ChaCha20.
+
+This function is conjectured to meet the standard notions of privacy and
+third-party unforgeability.
+*/
+use ffi::{crypto_box_curve25519xchacha20poly1305_keypair,
+          crypto_box_curve25519xchacha20poly1305_beforenm,
+          crypto_box_curve25519xchacha20poly1305_easy,
+          crypto_box_curve25519xchacha20poly1305_open_easy,
+          crypto_box_curve25519xchacha20poly1305_easy_afternm,
+          crypto_box_curve25519xchacha20poly1305_open_easy_afternm,
+          crypto_box_curve25519xchacha20poly1305_detached,
+          crypto_box_curve25519xchacha20poly1305_open_detached,
+          crypto_box_curve25519xchacha20poly1305_detached_afternm,
+          crypto_box_curve25519xchacha20poly1305_open_detached_afternm,
+          crypto_box_curve25519xchacha20poly1305_PUBLICKEYBYTES,
+          crypto_box_curve25519xchacha20poly1305_SECRETKEYBYTES,
+          crypto_box_curve25519xchacha20poly1305_NONCEBYTES,
+          crypto_box_curve25519xchacha20poly1305_BEFORENMBYTES,
+          crypto_box_curve25519xchacha20poly1305_MACBYTES};
+
+box_module!(crypto_box_curve25519xchacha20poly1305_keypair,
+            crypto_box_curve25519xchacha20poly1305_beforenm,
+            crypto_box_curve25519xchacha20poly1305_easy,
+            crypto_box_curve25519xchacha20poly1305_open_easy,
+            crypto_box_curve25519xchacha20poly1305_easy_afternm,
+            crypto_box_curve25519xchacha20poly1305_open_easy_afternm,
+            crypto_box_curve25519xchacha20poly1305_detached,
+            crypto_box_curve25519xchacha20poly1305_open_detached,
+            crypto_box_curve25519xchacha20poly1305_detached_afternm,
+            crypto_box_curve25519xchacha20poly1305_open_detached_afternm,
+            crypto_box_curve25519xchacha20poly1305_PUBLICKEYBYTES,
+            crypto_box_curve25519xchacha20poly1305_SECRETKEYBYTES,
+            crypto_box_curve25519xchacha20poly1305_NONCEBYTES,
+            crypto_box_curve25519xchacha20poly1305_BEFORENMBYTES,
+            crypto_box_curve25519xchacha20poly1305_MACBYTES);
+
+#[test]
+fn test_vector_1() {
+    // the keys, nonce and message of tests/box.c from NaCl, the ciphertext
+    // is the output of crypto_box_curve25519xchacha20poly1305_easy()
+    let alicesk = SecretKey([0x77,0x07,0x6d,0x0a,0x73,0x18,0xa5,0x7d,
+                             0x3c,0x16,0xc1,0x72,0x51,0xb2,0x66,0x45,
+                             0xdf,0x4c,0x2f,0x87,0xeb,0xc0,0x99,0x2a,
+                             0xb1,0x77,0xfb,0xa5,0x1d,0xb9,0x2c,0x2a]);
+    let bobpk   = PublicKey([0xde,0x9e,0xdb,0x7d,0x7b,0x7d,0xc1,0xb4,
+                             0xd3,0x5b,0x61,0xc2,0xec,0xe4,0x35,0x37,
+                             0x3f,0x83,0x43,0xc8,0x5b,0x78,0x67,0x4d,
+                             0xad,0xfc,0x7e,0x14,0x6f,0x88,0x2b,0x4f]);
+    let nonce   = Nonce([0x69,0x69,0x6e,0xe9,0x55,0xb6,0x2b,0x73,
+                         0xcd,0x62,0xbd,0xa8,0x75,0xfc,0x73,0xd6,
+                         0x82,0x19,0xe0,0x03,0x6b,0x7a,0x0b,0x37]);
+    let m = [0xbe,0x07,0x5f,0xc5,0x3c,0x81,0xf2,0xd5,
+             0xcf,0x14,0x13,0x16,0xeb,0xeb,0x0c,0x7b,
+             0x52,0x28,0xc5,0x2a,0x4c,0x62,0xcb,0xd4,
+             0x4b,0x66,0x84,0x9b,0x64,0x24,0x4f,0xfc,
+             0xe5,0xec,0xba,0xaf,0x33,0xbd,0x75,0x1a,
+             0x1a,0xc7,0x28,0xd4,0x5e,0x6c,0x61,0x29,
+             0x6c,0xdc,0x3c,0x01,0x23,0x35,0x61,0xf4,
+             0x1d,0xb6,0x6c,0xce,0x31,0x4a,0xdb,0x31,
+             0x0e,0x3b,0xe8,0x25,0x0c,0x46,0xf0,0x6d,
+             0xce,0xea,0x3a,0x7f,0xa1,0x34,0x80,0x57,
+             0xe2,0xf6,0x55,0x6a,0xd6,0xb1,0x31,0x8a,
+             0x02,0x4a,0x83,0x8f,0x21,0xaf,0x1f,0xde,
+             0x04,0x89,0x77,0xeb,0x48,0xf5,0x9f,0xfd,
+             0x49,0x24,0xca,0x1c,0x60,0x90,0x2e,0x52,
+             0xf0,0xa0,0x89,0xbc,0x76,0x89,0x70,0x40,
+             0xe0,0x82,0xf9,0x37,0x76,0x38,0x48,0x64,
+             0x5e,0x07,0x05];
+    let c = seal(&m, &nonce, &bobpk, &alicesk);
+    let pk = precompute(&bobpk, &alicesk);
+    let cpre = seal_precomputed(&m, &nonce, &pk);
+    let cexp = vec![0x0b,0x4f,0xf0,0x07,0x42,0xf3,0xc1,0xaa,
+                    0x99,0xa6,0x32,0x1e,0x38,0x83,0xb0,0x3c,
+                    0xef,0x2c,0x60,0x61,0xb7,0xbc,0xec,0x0c,
+                    0xfd,0x72,0x30,0x55,0xf6,0x1f,0x1c,0xcc,
+                    0xa2,0x94,0x6d,0x5a,0x04,0xdb,0xf8,0x31,
+                    0x51,0xf4,0x89,0x42,0x23,0xac,0x9b,0xc7,
+                    0x90,0xc6,0x60,0xe1,0x9d,0xc6,0x4c,0xc0,
+                    0xd7,0xf9,0xc7,0x68,0x9a,0xd1,0x90,0x99,
+                    0x55,0xe7,0xa9,0xa7,0xbd,0xac,0x77,0x7d,
+                    0x6a,0x79,0x67,0xd0,0x00,0xce,0x84,0x1f,
+                    0xc0,0xf8,0xf3,0x1d,0x6d,0x87,0x20,0xb3,
+                    0x74,0xbc,0x28,0x98,0x27,0x64,0xa7,0xdb,
+                    0xeb,0x4d,0xaa,0x97,0x7a,0xae,0x1b,0x73,
+                    0x50,0xfa,0xc7,0x9a,0x17,0x7e,0xce,0x75,
+                    0x41,0xfe,0xd2,0x64,0x46,0x1e,0xa9,0x3e,
+                    0x6d,0x7a,0xd9,0xfc,0x08,0xc9,0x1e,0x77,
+                    0x4a,0xbe,0x79,0x03,0x6e,0x7f,0x79,0xdd,
+                    0xcc,0x6a,0xab,0x3a,0xbf,0x52,0x76,0x31,
+                    0x04,0x8c,0xe1];
+    assert!(c == cexp);
+    assert!(cpre == cexp);
+}
+
+#[test]
+fn test_vector_2() {
+    // the keys of tests/box2.c from NaCl
+    let bobsk = SecretKey([0x5d,0xab,0x08,0x7e,0x62,0x4a,0x8a,0x4b,
+                           0x79,0xe1,0x7f,0x8b,0x83,0x80,0x0e,0xe6,
+                           0x6f,0x3b,0xb1,0x29,0x26,0x18,0xb6,0xfd,
+                           0x1c,0x2f,0x8b,0x27,0xff,0x88,0xe0,0xeb]);
+    let alicepk = PublicKey([0x85,0x20,0xf0,0x09,0x89,0x30,0xa7,0x54,
+                             0x74,0x8b,0x7d,0xdc,0xb4,0x3e,0xf7,0x5a,
+                             0x0d,0xbf,0x3a,0x0d,0x26,0x38,0x1a,0xf4,
+                             0xeb,0xa4,0xa9,0x8e,0xaa,0x9b,0x4e,0x6a]);
+    let nonce = Nonce([0x69,0x69,0x6e,0xe9,0x55,0xb6,0x2b,0x73,
+                       0xcd,0x62,0xbd,0xa8,0x75,0xfc,0x73,0xd6,
+                       0x82,0x19,0xe0,0x03,0x6b,0x7a,0x0b,0x37]);
+    let c = [0x0b,0x4f,0xf0,0x07,0x42,0xf3,0xc1,0xaa,
+             0x99,0xa6,0x32,0x1e,0x38,0x83,0xb0,0x3c,
+             0xef,0x2c,0x60,0x61,0xb7,0xbc,0xec,0x0c,
+             0xfd,0x72,0x30,0x55,0xf6,0x1f,0x1c,0xcc,
+             0xa2,0x94,0x6d,0x5a,0x04,0xdb,0xf8,0x31,
+             0x51,0xf4,0x89,0x42,0x23,0xac,0x9b,0xc7,
+             0x90,0xc6,0x60,0xe1,0x9d,0xc6,0x4c,0xc0,
+             0xd7,0xf9,0xc7,0x68,0x9a,0xd1,0x90,0x99,
+             0x55,0xe7,0xa9,0xa7,0xbd,0xac,0x77,0x7d,
+             0x6a,0x79,0x67,0xd0,0x00,0xce,0x84,0x1f,
+             0xc0,0xf8,0xf3,0x1d,0x6d,0x87,0x20,0xb3,
+             0x74,0xbc,0x28,0x98,0x27,0x64,0xa7,0xdb,
+             0xeb,0x4d,0xaa,0x97,0x7a,0xae,0x1b,0x73,
+             0x50,0xfa,0xc7,0x9a,0x17,0x7e,0xce,0x75,
+             0x41,0xfe,0xd2,0x64,0x46,0x1e,0xa9,0x3e,
+             0x6d,0x7a,0xd9,0xfc,0x08,0xc9,0x1e,0x77,
+             0x4a,0xbe,0x79,0x03,0x6e,0x7f,0x79,0xdd,
+             0xcc,0x6a,0xab,0x3a,0xbf,0x52,0x76,0x31,
+             0x04,0x8c,0xe1];
+    let mexp = Some(vec![0xbe,0x07,0x5f,0xc5,0x3c,0x81,0xf2,0xd5,
+                      0xcf,0x14,0x13,0x16,0xeb,0xeb,0x0c,0x7b,
+                      0x52,0x28,0xc5,0x2a,0x4c,0x62,0xcb,0xd4,
+                      0x4b,0x66,0x84,0x9b,0x64,0x24,0x4f,0xfc,
+                      0xe5,0xec,0xba,0xaf,0x33,0xbd,0x75,0x1a,
+                      0x1a,0xc7,0x28,0xd4,0x5e,0x6c,0x61,0x29,
+                      0x6c,0xdc,0x3c,0x01,0x23,0x35,0x61,0xf4,
+                      0x1d,0xb6,0x6c,0xce,0x31,0x4a,0xdb,0x31,
+                      0x0e,0x3b,0xe8,0x25,0x0c,0x46,0xf0,0x6d,
+                      0xce,0xea,0x3a,0x7f,0xa1,0x34,0x80,0x57,
+                      0xe2,0xf6,0x55,0x6a,0xd6,0xb1,0x31,0x8a,
+                      0x02,0x4a,0x83,0x8f,0x21,0xaf,0x1f,0xde,
+                      0x04,0x89,0x77,0xeb,0x48,0xf5,0x9f,0xfd,
+                      0x49,0x24,0xca,0x1c,0x60,0x90,0x2e,0x52,
+                      0xf0,0xa0,0x89,0xbc,0x76,0x89,0x70,0x40,
+                      0xe0,0x82,0xf9,0x37,0x76,0x38,0x48,0x64,
+                      0x5e,0x07,0x05]);
+    let m = open(&c, &nonce, &alicepk, &bobsk);
+    let pk = precompute(&alicepk, &bobsk);
+    let m_pre = open_precomputed(&c, &nonce, &pk);
+    assert!(m == mexp);
+    assert!(m_pre == mexp);
+}
@@ -7,275 +7,37 @@ This function is conjectured to meet the standard notions of privacy and
 third-party unforgeability.
 
 */
-use ffi;
-use std::intrinsics::volatile_set_memory;
-use std::ops::{Index, Range, RangeFrom, RangeFull, RangeTo};
-use utils::marshal;
-use randombytes::randombytes_into;
-
-pub const PUBLICKEYBYTES: usize = ffi::crypto_box_curve25519xsalsa20poly1305_PUBLICKEYBYTES;
-pub const SECRETKEYBYTES: usize = ffi::crypto_box_curve25519xsalsa20poly1305_SECRETKEYBYTES;
-pub const NONCEBYTES: usize = ffi::crypto_box_curve25519xsalsa20poly1305_NONCEBYTES;
-pub const PRECOMPUTEDKEYBYTES: usize = ffi::crypto_box_curve25519xsalsa20poly1305_BEFORENMBYTES;
-const ZEROBYTES: usize = ffi::crypto_box_curve25519xsalsa20poly1305_ZEROBYTES;
-const BOXZEROBYTES: usize = ffi::crypto_box_curve25519xsalsa20poly1305_BOXZEROBYTES;
-
-/**
- * `PublicKey` for asymmetric authenticated encryption
- */
-#[derive(Copy)]
-pub struct PublicKey(pub [u8; PUBLICKEYBYTES]);
-
-newtype_clone!(PublicKey);
-newtype_impl!(PublicKey, PUBLICKEYBYTES);
-
-/**
- * `SecretKey` for asymmetric authenticated encryption
- *
- * When a `SecretKey` goes out of scope its contents
- * will be zeroed out
- */
-pub struct SecretKey(pub [u8; SECRETKEYBYTES]);
-
-newtype_drop!(SecretKey);
-newtype_clone!(SecretKey);
-newtype_impl!(SecretKey, SECRETKEYBYTES);
-
-/**
- * `Nonce` for asymmetric authenticated encryption
- */
-#[derive(Copy)]
-pub struct Nonce(pub [u8; NONCEBYTES]);
-
-newtype_clone!(Nonce);
-newtype_impl!(Nonce, NONCEBYTES);
-
-/**
- * `gen_keypair()` randomly generates a secret key and a corresponding public key.
- *
- * THREAD SAFETY: `gen_keypair()` is thread-safe provided that you have
- * called `sodiumoxide::init()` once before using any other function
- * from sodiumoxide.
- */
-pub fn gen_keypair() -> (PublicKey, SecretKey) {
-    unsafe {
-        let mut pk = [0u8; PUBLICKEYBYTES];
-        let mut sk = [0u8; SECRETKEYBYTES];
-        ffi::crypto_box_curve25519xsalsa20poly1305_keypair(
-            &mut pk,
-            &mut sk);
-        (PublicKey(pk), SecretKey(sk))
-    }
-}
-
-/**
- * `gen_nonce()` randomly generates a nonce
- *
- * THREAD SAFETY: `gen_nonce()` is thread-safe provided that you have
- * called `sodiumoxide::init()` once before using any other function
- * from sodiumoxide.
- */
-pub fn gen_nonce() -> Nonce {
-    let mut n = [0; NONCEBYTES];
-    randombytes_into(&mut n);
-    Nonce(n)
-}
-
-/**
- * `seal()` encrypts and authenticates a message `m` using the senders secret key `sk`,
- * the receivers public key `pk` and a nonce `n`. It returns a ciphertext `c`.
- */
-pub fn seal(m: &[u8],
-            &Nonce(ref n): &Nonce,
-            &PublicKey(ref pk): &PublicKey,
-            &SecretKey(ref sk): &SecretKey) -> Vec<u8> {
-    let (c, _) = marshal(m, ZEROBYTES, BOXZEROBYTES, |dst, src, len| {
-        unsafe {
-            ffi::crypto_box_curve25519xsalsa20poly1305(dst,
-                                                       src,
-                                                       len,
-                                                       n,
-                                                       pk,
-                                                       sk);
-        }
-    });
-    c
-}
-
-/**
- * `open()` verifies and decrypts a ciphertext `c` using the receiver's secret key `sk`,
- * the senders public key `pk`, and a nonce `n`. It returns a plaintext `Some(m)`.
- * If the ciphertext fails verification, `open()` returns `None`.
- */
-pub fn open(c: &[u8],
-            &Nonce(ref n): &Nonce,
-            &PublicKey(ref pk): &PublicKey,
-            &SecretKey(ref sk): &SecretKey) -> Option<Vec<u8>> {
-    if c.len() < BOXZEROBYTES {
-        return None
-    }
-    let (m, ret) = marshal(c, BOXZEROBYTES, ZEROBYTES, |dst, src, len| {
-        unsafe {
-            ffi::crypto_box_curve25519xsalsa20poly1305_open(dst,
-                                                            src,
-                                                            len,
-                                                            n,
-                                                            pk,
-                                                            sk)
-        }
-    });
-    if ret == 0 {
-        Some(m)
-    } else {
-        None
-    }
-}
-
-/**
- * Applications that send several messages to the same receiver can gain speed by
- * splitting `seal()` into two steps, `precompute()` and `seal_precomputed()`.
- * Similarly, applications that receive several messages from the same sender can gain
- * speed by splitting `open()` into two steps, `precompute()` and `open_precomputed()`.
- *
- * When a `PrecomputedKey` goes out of scope its contents will be zeroed out
- */
-pub struct PrecomputedKey([u8; PRECOMPUTEDKEYBYTES]);
-
-newtype_drop!(PrecomputedKey);
-newtype_clone!(PrecomputedKey);
-newtype_impl!(PrecomputedKey, PRECOMPUTEDKEYBYTES);
-
-/**
- * `precompute()` computes an intermediate key that can be used by `seal_precomputed()`
- * and `open_precomputed()`
- */
-pub fn precompute(&PublicKey(ref pk): &PublicKey,
-                  &SecretKey(ref sk): &SecretKey) -> PrecomputedKey {
-    let mut k = [0u8; PRECOMPUTEDKEYBYTES];
-    unsafe {
-        ffi::crypto_box_curve25519xsalsa20poly1305_beforenm(&mut k,
-                                                            pk,
-                                                            sk);
-    }
-    PrecomputedKey(k)
-}
-
-/**
- * `seal_precomputed()` encrypts and authenticates a message `m` using a precomputed key `k`,
- * and a nonce `n`. It returns a ciphertext `c`.
- */
-pub fn seal_precomputed(m: &[u8],
-                        &Nonce(ref n): &Nonce,
-                        &PrecomputedKey(ref k): &PrecomputedKey) -> Vec<u8> {
-    let (c, _) = marshal(m, ZEROBYTES, BOXZEROBYTES, |dst, src, len| {
-        unsafe {
-            ffi::crypto_box_curve25519xsalsa20poly1305_afternm(dst,
-                                                               src,
-                                                               len,
-                                                               n,
-                                                               k);
-        }
-    });
-    c
-}
-
-/**
- * `open_precomputed()` verifies and decrypts a ciphertext `c` using a precomputed
- * key `k` and a nonce `n`. It returns a plaintext `Some(m)`.
- * If the ciphertext fails verification, `open_precomputed()` returns `None`.
- */
-pub fn open_precomputed(c: &[u8],
-                        &Nonce(ref n): &Nonce,
-                        &PrecomputedKey(ref k): &PrecomputedKey) -> Option<Vec<u8>> {
-    if c.len() < BOXZEROBYTES {
-        return None
-    }
-    let (m, ret) = marshal(c, BOXZEROBYTES, ZEROBYTES, |dst, src, len| {
-        unsafe {
-            ffi::crypto_box_curve25519xsalsa20poly1305_open_afternm(dst,
-                                                                    src,
-                                                                    len,
-                                                                    n,
-                                                                    k)
-        }
-    });
-    if ret == 0 {
-        Some(m)
-    } else {
-        None
-    }
-}
-
-#[test]
-fn test_seal_open() {
-    use randombytes::randombytes;
-    for i in (0..256us) {
-        let (pk1, sk1) = gen_keypair();
-        let (pk2, sk2) = gen_keypair();
-        let m = randombytes(i);
-        let n = gen_nonce();
-        let c = seal(&m, &n, &pk1, &sk2);
-        let opened = open(&c, &n, &pk2, &sk1);
-        assert!(Some(m) == opened);
-    }
-}
-
-#[test]
-fn test_seal_open_precomputed() {
-    use randombytes::randombytes;
-    for i in (0..256us) {
-        let (pk1, sk1) = gen_keypair();
-        let (pk2, sk2) = gen_keypair();
-        let k1 = precompute(&pk1, &sk2);
-        let PrecomputedKey(k1buf) = k1;
-        let k2 = precompute(&pk2, &sk1);
-        let PrecomputedKey(k2buf) = k2;
-        assert!(k1buf == k2buf);
-        let m = randombytes(i);
-        let n = gen_nonce();
-        let c = seal_precomputed(&m, &n, &k1);
-        let opened = open_precomputed(&c, &n, &k2);
-        assert!(Some(m) == opened);
-    }
-}
-
-#[test]
-fn test_seal_open_tamper() {
-    use randombytes::randombytes;
-    for i in (0..32us) {
-        let (pk1, sk1) = gen_keypair();
-        let (pk2, sk2) = gen_keypair();
-        let m = randombytes(i);
-        let n = gen_nonce();
-        let mut cv = seal(&m, &n, &pk1, &sk2);
-        let c = cv.as_mut_slice();
-        for j in (0..c.len()) {
-            c[j] ^= 0x20;
-            assert!(None == open(c, &n, &pk2, &sk1));
-            c[j] ^= 0x20;
-        }
-    }
-}
-
-#[test]
-fn test_seal_open_precomputed_tamper() {
-    use randombytes::randombytes;
-    for i in (0..32us) {
-        let (pk1, sk1) = gen_keypair();
-        let (pk2, sk2) = gen_keypair();
-        let k1 = precompute(&pk1, &sk2);
-        let k2 = precompute(&pk2, &sk1);
-        let m = randombytes(i);
-        let n = gen_nonce();
-        let mut cv = seal_precomputed(&m, &n, &k1);
-        let c = cv.as_mut_slice();
-        for j in (0..c.len()) {
-            c[j] ^= 0x20;
-            assert!(None == open_precomputed(c, &n, &k2));
-            c[j] ^= 0x20;
-        }
-    }
-}
+use ffi::{crypto_box_curve25519xsalsa20poly1305_keypair,
+          crypto_box_curve25519xsalsa20poly1305_beforenm,
+          crypto_box_easy,
+          crypto_box_open_easy,
+          crypto_box_easy_afternm,
+          crypto_box_open_easy_afternm,
+          crypto_box_detached,
+          crypto_box_open_detached,
+          crypto_box_detached_afternm,
+          crypto_box_open_detached_afternm,
+          crypto_box_curve25519xsalsa20poly1305_PUBLICKEYBYTES,
+          crypto_box_curve25519xsalsa20poly1305_SECRETKEYBYTES,
+          crypto_box_curve25519xsalsa20poly1305_NONCEBYTES,
+          crypto_box_curve25519xsalsa20poly1305_BEFORENMBYTES,
+          crypto_box_curve25519xsalsa20poly1305_MACBYTES};
+
+box_module!(crypto_box_curve25519xsalsa20poly1305_keypair,
+            crypto_box_curve25519xsalsa20poly1305_beforenm,
+            crypto_box_easy,
+            crypto_box_open_easy,
+            crypto_box_easy_afternm,
+            crypto_box_open_easy_afternm,
+            crypto_box_detached,
+            crypto_box_open_detached,
+            crypto_box_detached_afternm,
+            crypto_box_open_detached_afternm,
+            crypto_box_curve25519xsalsa20poly1305_PUBLICKEYBYTES,
+            crypto_box_curve25519xsalsa20poly1305_SECRETKEYBYTES,
+            crypto_box_curve25519xsalsa20poly1305_NONCEBYTES,
+            crypto_box_curve25519xsalsa20poly1305_BEFORENMBYTES,
+            crypto_box_curve25519xsalsa20poly1305_MACBYTES);
 
 #[test]
 fn test_vector_1() {
@@ -390,40 +152,3 @@ fn test_vector_2() {
     assert!(m == mexp);
     assert!(m_pre == mexp);
 }
-
-#[cfg(test)]
-mod bench {
-    extern crate test;
-    use randombytes::randombytes;
-    use super::*;
-
-    const BENCH_SIZES: [usize; 14] = [0, 1, 2, 4, 8, 16, 32, 64,
-                                      128, 256, 512, 1024, 2048, 4096];
-
-    #[bench]
-    fn bench_seal_open(b: &mut test::Bencher) {
-        let (pk, sk) = gen_keypair();
-        let n = gen_nonce();
-        let ms: Vec<Vec<u8>> = BENCH_SIZES.iter().map(|s| {
-            randombytes(*s)
-        }).collect();
-        b.iter(|| {
-            for m in ms.iter() {
-                open(&seal(m, &n, &pk, &sk), &n, &pk, &sk).unwrap();
-            }
-        });
-    }
-
-    #[bench]
-    fn bench_precompute(b: &mut test::Bencher) {
-        let (pk, sk) = gen_keypair();
-        b.iter(|| {
-            /* we do this benchmark as many times as the other benchmarks
-            so that we can compare the times */
-            for _ in BENCH_SIZES.iter() {
-                precompute(&pk, &sk);
-                precompute(&pk, &sk);
-            }
-        });
-    }
-}
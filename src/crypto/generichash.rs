@@ -0,0 +1,21 @@
+/*!
+Generic hashing
+
+# Security model
+`hash()` computes a fixed-length fingerprint for an arbitrary long message.
+It is suitable for file integrity checks, content addressing and for
+deriving unique identifiers. If a key is given, the output depends on the
+key as well, which makes `hash()` usable as a message authentication code.
+
+Unlike `crypto::hash`, the output length can be chosen by the caller,
+between `BYTES_MIN` and `BYTES_MAX` bytes. `BYTES` is the recommended
+minimum for collision resistance.
+
+# Selected primitive
+`hash()` is `crypto_generichash_blake2b`, BLAKE2b as specified in
+[RFC 7693](https://tools.ietf.org/html/rfc7693), which is faster than
+SHA-2 and SHA-3 on modern CPUs while being at least as secure as SHA-3.
+*/
+pub use self::blake2b::*;
+#[path="blake2b.rs"]
+pub mod blake2b;
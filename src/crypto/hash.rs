@@ -29,6 +29,14 @@ NaCl supports the following hash functions:
 |crypto_hash_sha256|SHA-256  |32   |
 |crypto_hash_sha512|SHA-512  |64   |
 ------------------------------------
+
+# Incremental hashing
+Messages that are too large to be kept in memory, or that arrive in pieces,
+can be hashed with a `State`: call `State::new()`, pass the message to
+`update()` one chunk at a time and get the `Digest` from `finalize()`.
+
+`State` implements `std::io::Write`, and `hash_reader()` hashes everything
+that can be read from a `std::io::Read`, such as a file or a socket.
 */
 pub use self::sha512::*;
 #[path="hash_macros.rs"]
@@ -1,6 +1,16 @@
-macro_rules! hash_module (($hash_name:ident, $hashbytes:expr, $blockbytes:expr) => (
+macro_rules! hash_module (($hash_name:ident,
+                           $state_name:ident,
+                           $init_name:ident,
+                           $update_name:ident,
+                           $final_name:ident,
+                           $hashbytes:expr,
+                           $blockbytes:expr) => (
 
 use std::ops::{Index, Range, RangeFrom, RangeFull, RangeTo};
+use std::intrinsics::volatile_set_memory;
+use std::mem;
+use std::io;
+use std::io::{Read, Write};
 use libc::c_ulonglong;
 
 pub const HASHBYTES: usize = $hashbytes;
@@ -26,6 +36,181 @@ pub fn hash(m: &[u8]) -> Digest {
     }
 }
 
+/**
+ * `State` for incremental hashing
+ *
+ * A `State` can be cloned in the middle of a computation, for example to
+ * compute the digests of several messages that share a common prefix.
+ *
+ * When a `State` goes out of scope its contents will be zeroed out
+ */
+pub struct State($state_name);
+
+impl State {
+    /**
+     * `new()` initializes a new `State`
+     */
+    pub fn new() -> State {
+        unsafe {
+            let mut st: $state_name = mem::zeroed();
+            $init_name(&mut st);
+            State(st)
+        }
+    }
+
+    /**
+     * `update()` feeds the next chunk `data` of the message into the `State`
+     */
+    pub fn update(&mut self, data: &[u8]) {
+        let &mut State(ref mut st) = self;
+        unsafe {
+            $update_name(st, data.as_ptr(), data.len() as c_ulonglong);
+        }
+    }
+
+    /**
+     * `finalize()` finishes the computation and returns the `Digest` of all
+     * data passed to `update()`
+     */
+    pub fn finalize(mut self) -> Digest {
+        let &mut State(ref mut st) = &mut self;
+        let mut h = [0; HASHBYTES];
+        unsafe {
+            $final_name(st, &mut h);
+        }
+        Digest(h)
+    }
+}
+
+impl Clone for State {
+    fn clone(&self) -> State {
+        let &State(st) = self;
+        State(st)
+    }
+}
+
+/**
+ * Writing to a `State` feeds the data into the hash computation, so that
+ * `io::copy()` can be used to hash anything that implements `io::Read`.
+ */
+impl Write for State {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/**
+ * `hash_reader()` reads `r` until EOF and returns the `Digest` of
+ * everything read.
+ * If reading from `r` fails, `hash_reader()` returns the error.
+ */
+pub fn hash_reader<R: Read>(r: &mut R) -> io::Result<Digest> {
+    let mut state = State::new();
+    try!(io::copy(r, &mut state));
+    Ok(state.finalize())
+}
+
+impl Drop for State {
+    fn drop(&mut self) {
+        let &mut State(ref mut st) = self;
+        unsafe {
+            volatile_set_memory(st as *mut $state_name as *mut u8, 0,
+                                mem::size_of::<$state_name>());
+        }
+    }
+}
+
+#[cfg(test)]
+fn hash_in_random_chunks(msg: &[u8]) -> Digest {
+    use randombytes::randombytes;
+    let mut state = State::new();
+    let mut pos = 0;
+    while pos < msg.len() {
+        let r = randombytes(2);
+        let chunk = ((r[0] as usize) << 8 | r[1] as usize) % (2 * BLOCKBYTES + 1);
+        let end = if pos + chunk > msg.len() { msg.len() } else { pos + chunk };
+        state.update(&msg[pos..end]);
+        pos = end;
+    }
+    state.finalize()
+}
+
+#[test]
+fn test_state_random_chunks() {
+    use randombytes::randombytes;
+    for i in (0..512us) {
+        let m = randombytes(i);
+        let Digest(h) = hash_in_random_chunks(&m);
+        let Digest(h_expected) = hash(&m);
+        assert!(h == h_expected);
+    }
+}
+
+#[test]
+fn test_state_clone() {
+    use randombytes::randombytes;
+    let prefix = randombytes(100);
+    let suffix1 = randombytes(200);
+    let suffix2 = randombytes(300);
+    let mut state1 = State::new();
+    state1.update(&prefix);
+    let mut state2 = state1.clone();
+    state1.update(&suffix1);
+    state2.update(&suffix2);
+    let mut m1 = prefix.clone();
+    m1.push_all(&suffix1);
+    let mut m2 = prefix.clone();
+    m2.push_all(&suffix2);
+    let Digest(h1) = state1.finalize();
+    let Digest(h2) = state2.finalize();
+    let Digest(h1_expected) = hash(&m1);
+    let Digest(h2_expected) = hash(&m2);
+    assert!(h1 == h1_expected);
+    assert!(h2 == h2_expected);
+}
+
+#[test]
+fn test_state_write() {
+    use randombytes::randombytes;
+    for i in (0..512us) {
+        let m = randombytes(i);
+        let mut state = State::new();
+        state.write_all(&m[..i / 2]).unwrap();
+        state.write_all(&m[i / 2..]).unwrap();
+        state.flush().unwrap();
+        let Digest(h) = state.finalize();
+        let Digest(h_expected) = hash(&m);
+        assert!(h == h_expected);
+    }
+}
+
+#[test]
+fn test_hash_reader() {
+    use randombytes::randombytes;
+    for i in (0..512us) {
+        let m = randombytes(i);
+        let Digest(h) = hash_reader(&mut &m[..]).unwrap();
+        let Digest(h_expected) = hash(&m);
+        assert!(h == h_expected);
+    }
+}
+
+#[test]
+fn test_hash_reader_error() {
+    struct FailingReader;
+    impl Read for FailingReader {
+        fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+            Err(io::Error::new(io::ErrorKind::Other, "read failed", None))
+        }
+    }
+    assert!(hash_reader(&mut FailingReader).is_err());
+}
+
 #[cfg(test)]
 mod bench {
     extern crate test;
@@ -46,6 +231,20 @@ mod bench {
             }
         });
     }
+
+    #[bench]
+    fn bench_state(b: &mut test::Bencher) {
+        let ms: Vec<Vec<u8>> = BENCH_SIZES.iter().map(|s| {
+            randombytes(*s)
+        }).collect();
+        b.iter(|| {
+            for m in ms.iter() {
+                let mut state = State::new();
+                state.update(&m);
+                state.finalize();
+            }
+        });
+    }
 }
 
 ));
@@ -3,18 +3,39 @@
 `HMAC-SHA-512`.  `HMAC-SHA-512-256` is conjectured to meet the standard notion
 of unforgeability.
 */
+#[cfg(test)]
+extern crate "rustc-serialize" as rustc_serialize;
 use ffi::{crypto_auth_hmacsha512256,
           crypto_auth_hmacsha512256_verify,
+          crypto_auth_hmacsha512256_state,
+          crypto_auth_hmacsha512256_init,
+          crypto_auth_hmacsha512256_update,
+          crypto_auth_hmacsha512256_final,
           crypto_auth_hmacsha512256_KEYBYTES,
           crypto_auth_hmacsha512256_BYTES};
 use crypto::verify::verify_32;
+use libc::{c_int, size_t};
+
+unsafe fn init_key(state: *mut crypto_auth_hmacsha512256_state,
+                   k: *const [u8; crypto_auth_hmacsha512256_KEYBYTES]) -> c_int {
+    crypto_auth_hmacsha512256_init(state, k as *const u8,
+                                   crypto_auth_hmacsha512256_KEYBYTES as size_t)
+}
 
 auth_module!(crypto_auth_hmacsha512256,
              crypto_auth_hmacsha512256_verify,
              verify_32,
+             crypto_auth_hmacsha512256_state,
+             init_key,
+             crypto_auth_hmacsha512256_update,
+             crypto_auth_hmacsha512256_final,
              crypto_auth_hmacsha512256_KEYBYTES,
              crypto_auth_hmacsha512256_BYTES);
 
+hmac_module!(crypto_auth_hmacsha512256_state,
+             crypto_auth_hmacsha512256_init,
+             3);
+
 #[test]
 fn test_vector_1() {
     // corresponding to tests/auth.c from NaCl
@@ -0,0 +1,52 @@
+/*!
+Helpers shared by the `Read`/`Write` adapters `secretstream::encrypted_io`
+and `secretbox::seekable`
+*/
+use std::io;
+use std::io::Read;
+
+pub fn invalid(desc: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, desc, None)
+}
+
+/// `read_full()` reads until `buf` is full or the reader hits EOF and
+/// returns the number of bytes read.
+pub fn read_full<R: Read>(r: &mut R, buf: &mut [u8]) -> io::Result<usize> {
+    let mut pos = 0;
+    while pos < buf.len() {
+        let n = try!(r.read(&mut buf[pos..]));
+        if n == 0 {
+            break
+        }
+        pos += n;
+    }
+    Ok(pos)
+}
+
+#[cfg(test)]
+pub fn read_all<R: Read>(r: io::Result<R>) -> io::Result<Vec<u8>> {
+    let mut r = try!(r);
+    let mut m = Vec::new();
+    try!(r.read_to_end(&mut m));
+    Ok(m)
+}
+
+/// `check_round_trip()` encrypts messages of 0 to 199 bytes with chunk
+/// sizes 1, 7 and 64. `round_trip` returns the ciphertext and the
+/// decrypted message; the ciphertext must be `prefix_len` bytes plus the
+/// message plus `chunk_overhead` bytes per chunk, where an empty message
+/// still takes one chunk.
+#[cfg(test)]
+pub fn check_round_trip<F>(prefix_len: usize, chunk_overhead: usize, mut round_trip: F)
+    where F: FnMut(&[u8], usize) -> (Vec<u8>, Vec<u8>) {
+    use randombytes::randombytes;
+    for &chunk_size in [1us, 7, 64].iter() {
+        for i in (0..200us) {
+            let m = randombytes(i);
+            let (c, m2) = round_trip(&m, chunk_size);
+            let chunks = if i == 0 { 1 } else { (i + chunk_size - 1) / chunk_size };
+            assert!(c.len() == prefix_len + i + chunks * chunk_overhead);
+            assert!(m2 == m);
+        }
+    }
+}
@@ -13,6 +13,15 @@ under the same key. Authenticators for two messages under the same key should
 be expected to reveal enough information to allow forgeries of authenticators
 on other messages.
 
+# Incremental authentication
+`State` computes the authenticator of a message that is passed to it in
+several chunks, e.g. associated data and ciphertext of a custom construction.
+The chunks are authenticated as if they had been concatenated, so the caller
+has to make the boundaries unambiguous, e.g. by padding or by appending their
+lengths. The one-time restriction applies to the `State` as well: a key must
+only be used for a single `State`. For the same reason `State` doesn't
+implement `Clone`.
+
 # Selected primitive
 `authenticate()` is `crypto_onetimeauth_poly1305`, an authenticator specified
 in [Cryptography in NaCl](http://nacl.cr.yp.to/valid.html), Section 9. This
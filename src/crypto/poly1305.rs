@@ -6,13 +6,21 @@ single message.
 */
 use ffi::{crypto_onetimeauth_poly1305,
           crypto_onetimeauth_poly1305_verify,
+          crypto_onetimeauth_poly1305_state,
+          crypto_onetimeauth_poly1305_init,
+          crypto_onetimeauth_poly1305_update,
+          crypto_onetimeauth_poly1305_final,
           crypto_onetimeauth_poly1305_KEYBYTES,
-           crypto_onetimeauth_poly1305_BYTES};
+          crypto_onetimeauth_poly1305_BYTES};
 use crypto::verify::verify_16;
 
 auth_module!(crypto_onetimeauth_poly1305,
              crypto_onetimeauth_poly1305_verify,
              verify_16,
+             crypto_onetimeauth_poly1305_state,
+             crypto_onetimeauth_poly1305_init,
+             crypto_onetimeauth_poly1305_update,
+             crypto_onetimeauth_poly1305_final,
              crypto_onetimeauth_poly1305_KEYBYTES,
              crypto_onetimeauth_poly1305_BYTES);
 
@@ -48,4 +56,8 @@ fn test_vector_1() {
     let a = authenticate(&c, &key);
     assert!(a == a_expected);
     assert!(verify(&a, &c, &key));
+    let mut state = State::init(&key);
+    state.update(&c[..32]);
+    state.update(&c[32..]);
+    assert!(state.verify(&a_expected));
 }
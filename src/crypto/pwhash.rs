@@ -0,0 +1,74 @@
+/*!
+Password hashing and key derivation
+
+# Security model
+Secret keys used to encrypt or sign confidential data have to be chosen from
+a very large keyspace. However, passwords are usually short, human-generated
+strings, making dictionary attacks practical.
+
+The `pwhash` operation derives a secret key of any size from a password and
+a salt.
+
+- The generated key has the size defined by the application, no matter what
+  the password length is.
+- The same password hashed with same parameters will always produce the same
+  key.
+- The same password hashed with different salts will produce different keys.
+- The function deriving a key from a password and a salt is CPU intensive
+  and intentionally requires a fair amount of memory. Therefore, it mitigates
+  brute-force attacks by requiring a significant effort to verify each
+  password.
+
+Common use cases:
+
+- Protecting an on-disk secret key with a password,
+- Password storage, or rather: storing what it takes to verify a password
+  without having to store the actual password.
+
+# Selected primitive
+`pwhash()` is `crypto_pwhash_scryptsalsa208sha256`, the scrypt function as
+specified by Colin Percival, using Salsa20/8 and SHA-256.
+
+`scrypt()` exposes the same function with explicit `n`, `r` and `p`
+parameters and an arbitrary salt, as specified in RFC 7914, for formats that
+store these parameters instead of an opslimit and a memlimit.
+
+# Alternate primitives
+Sodium supports the following password hashing functions:
+
+----------------------------------------------------------------------------
+|crypto_pwhash                     |primitive          |SALTBYTES|STRBYTES|
+|----------------------------------|-------------------|---------|--------|
+|crypto_pwhash_scryptsalsa208sha256|scrypt             |32       |102     |
+|crypto_pwhash_argon2id13          |Argon2id, v1.3     |16       |128     |
+|crypto_pwhash_argon2i13           |Argon2i, v1.3      |16       |128     |
+----------------------------------------------------------------------------
+
+`argon2id13` is Argon2id, a variant of the Password Hashing Competition
+winner, and is the recommended choice for new applications. `argon2i13` only
+uses data-independent memory accesses. Both modules additionally provide
+`OPSLIMIT_MODERATE` and `MEMLIMIT_MODERATE`. Their hashed passwords start with
+`$argon2id$` and `$argon2i$` respectively, and can be verified by any other
+Argon2 implementation.
+
+# Upgrading stored hashes
+Every module provides `needs_rehash()`, which tells whether a stored `$7$`,
+`$argon2i$` or `$argon2id$` hashed password was computed with a different
+algorithm or weaker cost parameters than the module's `pwhash()` would use
+with the given opslimit and memlimit, and `verify_and_upgrade()`, which
+verifies a password and returns a new hash whenever the stored one should be
+replaced. To migrate from scrypt to Argon2id, use the functions of
+`argon2id13` with `argon2id13` limits.
+*/
+pub use self::scryptsalsa208sha256::*;
+#[path="argon2_macros.rs"]
+#[macro_use]
+mod argon2_macros;
+#[path="scryptsalsa208sha256.rs"]
+pub mod scryptsalsa208sha256;
+#[path="argon2id13.rs"]
+pub mod argon2id13;
+#[path="argon2i13.rs"]
+pub mod argon2i13;
+#[path="pwhash_rehash.rs"]
+mod rehash;
@@ -0,0 +1,449 @@
+/*!
+Parsing stored password hashes
+
+The `needs_rehash()` and `verify_and_upgrade()` functions of
+`scryptsalsa208sha256`, `argon2i13` and `argon2id13` accept a hashed password
+produced by any of these modules. This module parses such a string into its
+algorithm and cost parameters, compares them against the policy of the target
+module and verifies a password against it.
+*/
+use super::scryptsalsa208sha256;
+use super::argon2i13;
+use super::argon2id13;
+
+#[derive(PartialEq)]
+pub enum Algorithm {
+    Argon2i,
+    Argon2id,
+}
+
+/// The algorithm and cost parameters of a stored hashed password
+pub enum Params {
+    Scrypt { n_log2: u32, r: u32, p: u32 },
+    Argon2 { alg: Algorithm, version: u64, m_kib: u64, t: u64 },
+}
+
+const ARGON2_VERSION_10: u64 = 0x10;
+const ARGON2_VERSION_13: u64 = 0x13;
+const SCRYPT_HASHCHARS: usize = 43;
+const ITOA64: &'static [u8] =
+    b"./0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+fn strip_nul(s: &[u8]) -> &[u8] {
+    match s.iter().position(|&c| c == 0) {
+        Some(n) => &s[..n],
+        None => s,
+    }
+}
+
+fn decode64_one(c: u8) -> Option<u32> {
+    ITOA64.iter().position(|&d| d == c).map(|n| n as u32)
+}
+
+fn is_itoa64(c: u8) -> bool {
+    decode64_one(c).is_some()
+}
+
+fn is_base64(c: u8) -> bool {
+    c == b'+' || c == b'/' ||
+    (c >= b'0' && c <= b'9') ||
+    (c >= b'A' && c <= b'Z') ||
+    (c >= b'a' && c <= b'z')
+}
+
+// 30-bit little-endian integer in 5 characters of the crypt(3) alphabet
+fn decode64_uint32(s: &[u8]) -> Result<u32, ()> {
+    let mut n = 0u32;
+    for (i, &c) in s.iter().enumerate() {
+        match decode64_one(c) {
+            Some(d) => n |= d << (6 * i),
+            None => return Err(()),
+        }
+    }
+    Ok(n)
+}
+
+fn expect(s: &mut &[u8], lit: &[u8]) -> Result<(), ()> {
+    let t = *s;
+    if !t.starts_with(lit) {
+        return Err(())
+    }
+    *s = &t[lit.len()..];
+    Ok(())
+}
+
+fn number(s: &mut &[u8]) -> Result<u64, ()> {
+    let t = *s;
+    let len = t.iter().take_while(|&&c| c >= b'0' && c <= b'9').count();
+    if len == 0 || (len > 1 && t[0] == b'0') {
+        return Err(())
+    }
+    let mut n = 0u64;
+    for &c in t[..len].iter() {
+        let d = (c - b'0') as u64;
+        n = match n.checked_mul(10).and_then(|n| n.checked_add(d)) {
+            Some(n) => n,
+            None => return Err(()),
+        };
+    }
+    *s = &t[len..];
+    Ok(n)
+}
+
+fn base64(s: &mut &[u8]) -> Result<(), ()> {
+    let t = *s;
+    let len = t.iter().take_while(|&&c| is_base64(c)).count();
+    if len == 0 {
+        return Err(())
+    }
+    *s = &t[len..];
+    Ok(())
+}
+
+// N_log2(1) r(5) p(5) salt $ hash(43), all in the crypt(3) alphabet
+fn parse_scrypt(s: &[u8]) -> Result<Params, ()> {
+    if s.len() < 11 {
+        return Err(())
+    }
+    let n_log2 = match decode64_one(s[0]) {
+        Some(n) if n > 0 && n < 64 => n,
+        _ => return Err(()),
+    };
+    let r = try!(decode64_uint32(&s[1..6]));
+    let p = try!(decode64_uint32(&s[6..11]));
+    if r == 0 || p == 0 {
+        return Err(())
+    }
+    let rest = &s[11..];
+    let salt_len = match rest.iter().position(|&c| c == b'$') {
+        Some(n) => n,
+        None => return Err(()),
+    };
+    let hash = &rest[salt_len + 1..];
+    if !rest[..salt_len].iter().all(|&c| is_itoa64(c)) ||
+       hash.len() != SCRYPT_HASHCHARS ||
+       !hash.iter().all(|&c| is_itoa64(c)) {
+        return Err(())
+    }
+    Ok(Params::Scrypt { n_log2: n_log2, r: r, p: p })
+}
+
+// [v=V$]m=M,t=T,p=P$salt$hash, salt and hash in unpadded base64
+fn parse_argon2(alg: Algorithm, mut s: &[u8]) -> Result<Params, ()> {
+    let version = if s.starts_with(b"v=") {
+        try!(expect(&mut s, b"v="));
+        let v = try!(number(&mut s));
+        try!(expect(&mut s, b"$"));
+        v
+    } else {
+        ARGON2_VERSION_10
+    };
+    try!(expect(&mut s, b"m="));
+    let m = try!(number(&mut s));
+    try!(expect(&mut s, b",t="));
+    let t = try!(number(&mut s));
+    try!(expect(&mut s, b",p="));
+    try!(number(&mut s));
+    try!(expect(&mut s, b"$"));
+    try!(base64(&mut s));
+    try!(expect(&mut s, b"$"));
+    try!(base64(&mut s));
+    if !s.is_empty() {
+        return Err(())
+    }
+    Ok(Params::Argon2 { alg: alg, version: version, m_kib: m, t: t })
+}
+
+/// Parses a hashed password produced by any of the pwhash modules.
+pub fn parse(s: &[u8]) -> Result<Params, ()> {
+    let scrypt_prefix = scryptsalsa208sha256::STRPREFIX.as_bytes();
+    let argon2i_prefix = argon2i13::STRPREFIX.as_bytes();
+    let argon2id_prefix = argon2id13::STRPREFIX.as_bytes();
+    if s.starts_with(scrypt_prefix) {
+        parse_scrypt(&s[scrypt_prefix.len()..])
+    } else if s.starts_with(argon2id_prefix) {
+        parse_argon2(Algorithm::Argon2id, &s[argon2id_prefix.len()..])
+    } else if s.starts_with(argon2i_prefix) {
+        parse_argon2(Algorithm::Argon2i, &s[argon2i_prefix.len()..])
+    } else {
+        Err(())
+    }
+}
+
+/// Returns `true` if `params` weren't computed with the Argon2 variant `alg`,
+/// with Argon2 version 1.3 and at least the given opslimit and memlimit.
+pub fn argon2_is_weaker(params: &Params,
+                        alg: Algorithm,
+                        opslimit: usize,
+                        memlimit: usize) -> bool {
+    match *params {
+        Params::Argon2 { alg: ref a, version, m_kib, t } => {
+            *a != alg ||
+            version != ARGON2_VERSION_13 ||
+            t < opslimit as u64 ||
+            m_kib < (memlimit / 1024) as u64
+        }
+        Params::Scrypt { .. } => true,
+    }
+}
+
+// the parameter choice of libsodium's crypto_pwhash_scryptsalsa208sha256
+fn scrypt_pick_params(opslimit: usize, memlimit: usize) -> (u32, u32, u32) {
+    let opslimit = if opslimit < 32768 { 32768 } else { opslimit as u64 };
+    let memlimit = memlimit as u64;
+    let r = 8u32;
+    let max_n = if opslimit < memlimit / 32 {
+        opslimit / (r as u64 * 4)
+    } else {
+        memlimit / (r as u64 * 128)
+    };
+    let mut n_log2 = 1u32;
+    while n_log2 < 63 && (1u64 << n_log2) <= max_n / 2 {
+        n_log2 += 1;
+    }
+    let p = if opslimit < memlimit / 32 {
+        1
+    } else {
+        let mut max_rp = (opslimit / 4) / (1u64 << n_log2);
+        if max_rp > 0x3fffffff {
+            max_rp = 0x3fffffff;
+        }
+        max_rp as u32 / r
+    };
+    (n_log2, r, p)
+}
+
+fn mul_saturating(a: u64, b: u64) -> u64 {
+    a.checked_mul(b).unwrap_or(::std::u64::MAX)
+}
+
+/// Returns `true` if `params` weren't computed with scrypt, or with less
+/// memory (`N * r`) or fewer computations (`N * r * p`) than
+/// `scryptsalsa208sha256::derive_key()` chooses for the given opslimit and
+/// memlimit.
+pub fn scrypt_is_weaker(params: &Params,
+                        opslimit: usize,
+                        memlimit: usize) -> bool {
+    match *params {
+        Params::Scrypt { n_log2, r, p } => {
+            let (policy_n_log2, policy_r, policy_p) =
+                scrypt_pick_params(opslimit, memlimit);
+            let mem = mul_saturating(1u64 << n_log2, r as u64);
+            let policy_mem = mul_saturating(1u64 << policy_n_log2,
+                                            policy_r as u64);
+            mem < policy_mem ||
+            mul_saturating(mem, p as u64) <
+                mul_saturating(policy_mem, policy_p as u64)
+        }
+        Params::Argon2 { .. } => true,
+    }
+}
+
+// copies s into the zero-filled dst, leaving room for the terminating zero
+fn fill(dst: &mut [u8], s: &[u8]) -> bool {
+    if s.len() >= dst.len() {
+        return false
+    }
+    for (d, &c) in dst.iter_mut().zip(s.iter()) {
+        *d = c;
+    }
+    true
+}
+
+// verifies passwd against the hashed password s that was parsed into params
+fn verify(params: &Params, s: &[u8], passwd: &[u8]) -> bool {
+    match *params {
+        Params::Scrypt { .. } => {
+            let mut hp = scryptsalsa208sha256::HashedPassword(
+                [0; scryptsalsa208sha256::HASHEDPASSWORDBYTES]);
+            {
+                let scryptsalsa208sha256::HashedPassword(ref mut b) = hp;
+                if !fill(b, s) {
+                    return false
+                }
+            }
+            scryptsalsa208sha256::pwhash_verify(&hp, passwd)
+        }
+        Params::Argon2 { alg: Algorithm::Argon2i, .. } => {
+            let mut hp = argon2i13::HashedPassword(
+                [0; argon2i13::HASHEDPASSWORDBYTES]);
+            {
+                let argon2i13::HashedPassword(ref mut b) = hp;
+                if !fill(b, s) {
+                    return false
+                }
+            }
+            argon2i13::pwhash_verify(&hp, passwd)
+        }
+        Params::Argon2 { alg: Algorithm::Argon2id, .. } => {
+            let mut hp = argon2id13::HashedPassword(
+                [0; argon2id13::HASHEDPASSWORDBYTES]);
+            {
+                let argon2id13::HashedPassword(ref mut b) = hp;
+                if !fill(b, s) {
+                    return false
+                }
+            }
+            argon2id13::pwhash_verify(&hp, passwd)
+        }
+    }
+}
+
+/// Parses the stored hashed password `stored`, which may be followed by zero
+/// bytes, and returns `is_weaker` applied to its parameters.
+pub fn needs_rehash<W>(stored: &[u8], is_weaker: W) -> Result<bool, ()>
+    where W: Fn(&Params) -> bool {
+    parse(strip_nul(stored)).map(|p| is_weaker(&p))
+}
+
+/// Verifies `passwd` against the stored hashed password `stored` and, if
+/// `is_weaker` is true for its parameters, returns the result of `pwhash`.
+pub fn verify_and_upgrade<H, W, F>(stored: &[u8],
+                                   passwd: &[u8],
+                                   is_weaker: W,
+                                   pwhash: F) -> Result<Option<H>, ()>
+    where W: Fn(&Params) -> bool, F: FnOnce() -> Result<H, ()> {
+    let s = strip_nul(stored);
+    let p = try!(parse(s));
+    if !verify(&p, s, passwd) {
+        return Err(())
+    }
+    if !is_weaker(&p) {
+        return Ok(None)
+    }
+    pwhash().map(Some)
+}
+
+#[cfg(test)]
+const SCRYPT_KNOWN: &'static [u8] = b"$7$C6..../....eUbCxNYbVsvLPRB2q8mQ9QmxPkDBrK93onhzyE3AX08$tE9.zkjTJLzThzuJ6jFA4ltvWNARONSaMK/.iBKPtlD";
+
+#[cfg(test)]
+const ARGON2I_KNOWN: &'static [u8] = b"$argon2i$v=19$m=256,t=3,p=1$WmUDK4uiESBuX720nxziUQ$Mz3BNn0SL6K8y7gFOgCDdoeQ/rO0fCyMXxy6NnkOsio";
+
+#[cfg(test)]
+const ARGON2ID_KNOWN: &'static [u8] = b"$argon2id$v=19$m=256,t=3,p=1$CHpVvNPYdHW46pV04gCZgA$IKP/Y9FXxcph+//VY6bIGkXi7o1QTYJPg1yYQq17e6g";
+
+#[test]
+fn test_needs_rehash_argon2id() {
+    use super::argon2id13::{needs_rehash, OpsLimit, MemLimit};
+    let s = ARGON2ID_KNOWN;
+    assert!(needs_rehash(s, OpsLimit(3), MemLimit(262144)) == Ok(false));
+    assert!(needs_rehash(s, OpsLimit(2), MemLimit(131072)) == Ok(false));
+    assert!(needs_rehash(s, OpsLimit(4), MemLimit(262144)) == Ok(true));
+    assert!(needs_rehash(s, OpsLimit(3), MemLimit(524288)) == Ok(true));
+}
+
+#[test]
+fn test_needs_rehash_scrypt() {
+    use super::scryptsalsa208sha256::{needs_rehash, OpsLimit, MemLimit,
+                                      OPSLIMIT_INTERACTIVE,
+                                      MEMLIMIT_INTERACTIVE,
+                                      OPSLIMIT_SENSITIVE,
+                                      MEMLIMIT_SENSITIVE};
+    // SCRYPT_KNOWN has N = 2^14, r = 8, p = 1
+    let s = SCRYPT_KNOWN;
+    assert!(needs_rehash(s, OPSLIMIT_INTERACTIVE,
+                         MEMLIMIT_INTERACTIVE) == Ok(false));
+    assert!(needs_rehash(s, OPSLIMIT_SENSITIVE,
+                         MEMLIMIT_SENSITIVE) == Ok(true));
+    // N = 2^13, r = 8, p = 1
+    assert!(needs_rehash(s, OpsLimit(262144), MemLimit(8388608)) == Ok(false));
+    // N = 2^14, r = 8, p = 64
+    assert!(needs_rehash(s, OpsLimit(33554432), MemLimit(16777216)) == Ok(true));
+}
+
+#[test]
+fn test_needs_rehash_algorithm() {
+    assert!(argon2id13::needs_rehash(SCRYPT_KNOWN, argon2id13::OpsLimit(1),
+                                     argon2id13::MemLimit(8192)) == Ok(true));
+    assert!(argon2id13::needs_rehash(ARGON2I_KNOWN, argon2id13::OpsLimit(1),
+                                     argon2id13::MemLimit(8192)) == Ok(true));
+    assert!(argon2i13::needs_rehash(ARGON2ID_KNOWN, argon2i13::OpsLimit(1),
+                                    argon2i13::MemLimit(8192)) == Ok(true));
+    assert!(argon2i13::needs_rehash(ARGON2I_KNOWN, argon2i13::OpsLimit(1),
+                                    argon2i13::MemLimit(8192)) == Ok(false));
+    assert!(scryptsalsa208sha256::needs_rehash(
+                ARGON2ID_KNOWN, scryptsalsa208sha256::OpsLimit(1),
+                scryptsalsa208sha256::MemLimit(1)) == Ok(true));
+}
+
+#[test]
+fn test_needs_rehash_version() {
+    use super::argon2id13::{needs_rehash, OpsLimit, MemLimit};
+    let s = b"$argon2id$m=256,t=3,p=1$CHpVvNPYdHW46pV04gCZgA$IKP/Y9FXxcph+//VY6bIGkXi7o1QTYJPg1yYQq17e6g";
+    assert!(needs_rehash(s, OpsLimit(1), MemLimit(8192)) == Ok(true));
+}
+
+#[test]
+fn test_needs_rehash_hashed_password() {
+    use super::argon2id13::{pwhash, needs_rehash, OpsLimit, MemLimit};
+    let pwh = pwhash(b"Correct Horse Battery Staple",
+                     OpsLimit(3), MemLimit(262144)).unwrap();
+    assert!(needs_rehash(&pwh[..], OpsLimit(3), MemLimit(262144)) == Ok(false));
+    assert!(needs_rehash(&pwh[..], OpsLimit(4), MemLimit(262144)) == Ok(true));
+}
+
+#[test]
+fn test_parse_malformed() {
+    let malformed: [&[u8]; 11] = [
+        &b""[..],
+        &b"password"[..],
+        &b"$7$"[..],
+        &b"$7$C6..../....eUbCxNYbVsvLPRB2q8mQ9QmxPkDBrK93onhzyE3AX08"[..],
+        &b"$7$C...../....eUbCxNYbVsvLPRB2q8mQ9QmxPkDBrK93onhzyE3AX08$tE9.zkjTJLzThzuJ6jFA4ltvWNARONSaMK/.iBKPtlD"[..],
+        &b"$7$.6..../....eUbCxNYbVsvLPRB2q8mQ9QmxPkDBrK93onhzyE3AX08$tE9.zkjTJLzThzuJ6jFA4ltvWNARONSaMK/.iBKPtlD"[..],
+        &b"$argon2d$v=19$m=256,t=3,p=1$CHpVvNPYdHW46pV04gCZgA$IKP/Y9FXxcph"[..],
+        &b"$argon2id$v=19$m=256,t=3"[..],
+        &b"$argon2id$v=19$m=256,t=3,p=1$CHpVvNPYdHW46pV04gCZgA$"[..],
+        &b"$argon2id$v=19$m=,t=3,p=1$CHpVvNPYdHW46pV04gCZgA$IKP/Y9FXxcph"[..],
+        &b"$argon2id$v=19$m=99999999999999999999,t=3,p=1$CHpV$IKP/"[..],
+    ];
+    for s in malformed.iter() {
+        assert!(parse(*s).is_err());
+    }
+    let mut s = ARGON2ID_KNOWN.to_vec();
+    s.push(b'$');
+    assert!(parse(&s).is_err());
+}
+
+#[test]
+fn test_verify_and_upgrade() {
+    use super::argon2id13::{verify_and_upgrade, needs_rehash, pwhash_verify,
+                            OpsLimit, MemLimit, STRPREFIX};
+    let pw = b"Correct Horse Battery Staple";
+    for &s in [SCRYPT_KNOWN, ARGON2I_KNOWN].iter() {
+        let pwh = verify_and_upgrade(s, pw, OpsLimit(3), MemLimit(262144))
+                      .unwrap().unwrap();
+        assert!(&pwh[..STRPREFIX.len()] == STRPREFIX.as_bytes());
+        assert!(pwhash_verify(&pwh, pw));
+        assert!(needs_rehash(&pwh[..], OpsLimit(3), MemLimit(262144)) == Ok(false));
+    }
+    assert!(verify_and_upgrade(ARGON2ID_KNOWN, pw,
+                               OpsLimit(3), MemLimit(262144)).unwrap().is_none());
+    let pwh = verify_and_upgrade(ARGON2ID_KNOWN, pw,
+                                 OpsLimit(4), MemLimit(262144)).unwrap().unwrap();
+    assert!(pwhash_verify(&pwh, pw));
+}
+
+#[test]
+fn test_verify_and_upgrade_scrypt() {
+    use super::scryptsalsa208sha256::{verify_and_upgrade, pwhash_verify,
+                                      OPSLIMIT_INTERACTIVE,
+                                      MEMLIMIT_INTERACTIVE, STRPREFIX};
+    let pw = b"Correct Horse Battery Staple";
+    assert!(verify_and_upgrade(SCRYPT_KNOWN, pw, OPSLIMIT_INTERACTIVE,
+                               MEMLIMIT_INTERACTIVE).unwrap().is_none());
+    let pwh = verify_and_upgrade(ARGON2ID_KNOWN, pw, OPSLIMIT_INTERACTIVE,
+                                 MEMLIMIT_INTERACTIVE).unwrap().unwrap();
+    assert!(&pwh[..STRPREFIX.len()] == STRPREFIX.as_bytes());
+    assert!(pwhash_verify(&pwh, pw));
+}
+
+#[test]
+fn test_verify_and_upgrade_wrong_password() {
+    use super::argon2id13::{verify_and_upgrade, OpsLimit, MemLimit};
+    let pw = b"Correct Horse Battery Stapler";
+    for &s in [SCRYPT_KNOWN, ARGON2I_KNOWN, ARGON2ID_KNOWN].iter() {
+        assert!(verify_and_upgrade(s, pw, OpsLimit(3), MemLimit(262144)).is_err());
+    }
+}
@@ -5,6 +5,7 @@ cipher is conjectured to meet the standard notion of unpredictability.
 */
 use ffi::{crypto_stream_salsa20,
           crypto_stream_salsa20_xor,
+          crypto_stream_salsa20_xor_ic,
           crypto_stream_salsa20_KEYBYTES,
           crypto_stream_salsa20_NONCEBYTES};
 
@@ -13,6 +14,8 @@ stream_module!(crypto_stream_salsa20,
                crypto_stream_salsa20_KEYBYTES,
                crypto_stream_salsa20_NONCEBYTES);
 
+stream_ic_module!(crypto_stream_salsa20_xor_ic);
+
 #[test]
 fn test_vector_1() {
     // corresponding to tests/stream2.c and tests/stream6.cpp from NaCl
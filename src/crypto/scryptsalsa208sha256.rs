@@ -0,0 +1,438 @@
+/*!
+`crypto_pwhash_scryptsalsa208sha256`, a particular combination of Scrypt,
+Salsa20/8 and SHA-256
+*/
+use ffi;
+use libc::{c_char, c_ulonglong, size_t};
+use std::iter::repeat;
+use std::ops::{Index, Range, RangeFrom, RangeFull, RangeTo};
+use randombytes::randombytes_into;
+use super::rehash;
+
+pub const SALTBYTES: usize = ffi::crypto_pwhash_scryptsalsa208sha256_SALTBYTES;
+pub const HASHEDPASSWORDBYTES: usize =
+    ffi::crypto_pwhash_scryptsalsa208sha256_STRBYTES;
+pub const STRPREFIX: &'static str =
+    ffi::crypto_pwhash_scryptsalsa208sha256_STRPREFIX;
+
+/**
+ * `OpsLimit` represents the maximum number of computations to perform when
+ * using the functions in this module.
+ *
+ * A high `OpsLimit` will make the functions
+ * require more CPU cycles
+ */
+#[derive(Copy)]
+pub struct OpsLimit(pub usize);
+
+newtype_clone!(OpsLimit);
+
+/**
+ * `MemLimit` represents the maximum amount of RAM that the functions in this
+ * module will use, in bytes.
+ *
+ * It is highly recommended to allow the functions to use
+ * at least 16 megabytes.
+ */
+#[derive(Copy)]
+pub struct MemLimit(pub usize);
+
+newtype_clone!(MemLimit);
+
+/**
+ * `OPSLIMIT_INTERACTIVE` is a safe baseline for interactive,
+ * online operations.
+ */
+pub const OPSLIMIT_INTERACTIVE: OpsLimit =
+    OpsLimit(ffi::crypto_pwhash_scryptsalsa208sha256_OPSLIMIT_INTERACTIVE);
+
+/**
+ * `MEMLIMIT_INTERACTIVE` is a safe baseline for interactive,
+ * online operations.
+ */
+pub const MEMLIMIT_INTERACTIVE: MemLimit =
+    MemLimit(ffi::crypto_pwhash_scryptsalsa208sha256_MEMLIMIT_INTERACTIVE);
+
+/**
+ * Using `OPSLIMIT_SENSITIVE` and `MEMLIMIT_SENSITIVE` for highly
+ * sensitive data, e.g. a key protecting other secret keys, makes
+ * deriving a key take about 2 seconds on a 2.8 GHz Core i7 CPU and
+ * requires up to 1 gigabyte of dedicated RAM.
+ */
+pub const OPSLIMIT_SENSITIVE: OpsLimit =
+    OpsLimit(ffi::crypto_pwhash_scryptsalsa208sha256_OPSLIMIT_SENSITIVE);
+
+/**
+ * Using `OPSLIMIT_SENSITIVE` and `MEMLIMIT_SENSITIVE` for highly
+ * sensitive data, e.g. a key protecting other secret keys, makes
+ * deriving a key take about 2 seconds on a 2.8 GHz Core i7 CPU and
+ * requires up to 1 gigabyte of dedicated RAM.
+ */
+pub const MEMLIMIT_SENSITIVE: MemLimit =
+    MemLimit(ffi::crypto_pwhash_scryptsalsa208sha256_MEMLIMIT_SENSITIVE);
+
+/**
+ * `Salt` used for password hashing
+ */
+#[derive(Copy)]
+pub struct Salt(pub [u8; SALTBYTES]);
+
+newtype_clone!(Salt);
+newtype_impl!(Salt, SALTBYTES);
+
+/**
+ * `HashedPassword` is a password verifier generated from a password
+ *
+ * A `HashedPassword` is zero-terminated, includes only ASCII characters and
+ * can be conveniently stored into SQL databases and other data stores. No
+ * additional information has to be stored in order to verify the password.
+ */
+#[derive(Copy)]
+pub struct HashedPassword(pub [u8; HASHEDPASSWORDBYTES]);
+
+newtype_clone!(HashedPassword);
+newtype_impl!(HashedPassword, HASHEDPASSWORDBYTES);
+
+/**
+ * `gen_salt()` randomly generates a new `Salt` for key derivation
+ *
+ * THREAD SAFETY: `gen_salt()` is thread-safe provided that you have
+ * called `sodiumoxide::init()` once before using any other function
+ * from sodiumoxide.
+ */
+pub fn gen_salt() -> Salt {
+    let mut salt = [0; SALTBYTES];
+    randombytes_into(&mut salt);
+    Salt(salt)
+}
+
+/**
+ * `derive_key()` derives a key from a password and a `Salt`
+ *
+ * The computed key is stored into key.
+ *
+ * `opslimit` represents a maximum amount of computations to perform. Raising
+ * this number will make the function require more CPU cycles to compute a key.
+ *
+ * `memlimit` is the maximum amount of RAM that the function will use, in
+ * bytes. It is highly recommended to allow the function to use at least 16
+ * megabytes.
+ *
+ * The function returns `Ok(key)` on success and `Err(())` if the computation
+ * didn't complete, usually because the operating system refused to allocate
+ * the amount of requested memory.
+ */
+pub fn derive_key<'a>(key: &'a mut [u8],
+                      passwd: &[u8],
+                      &Salt(ref sb): &Salt,
+                      OpsLimit(opslimit): OpsLimit,
+                      MemLimit(memlimit): MemLimit) -> Result<&'a [u8], ()> {
+    let res = unsafe {
+        ffi::crypto_pwhash_scryptsalsa208sha256(key.as_mut_ptr(),
+                                                key.len() as c_ulonglong,
+                                                passwd.as_ptr() as *const c_char,
+                                                passwd.len() as c_ulonglong,
+                                                sb,
+                                                opslimit as c_ulonglong,
+                                                memlimit as size_t)
+    };
+    if res == 0 {
+        Ok(key)
+    } else {
+        Err(())
+    }
+}
+
+/**
+ * `pwhash()` returns a `HashedPassword` which
+ * includes:
+ *
+ * - the result of a memory-hard, CPU-intensive hash function applied to the
+ *   password `passwd`
+ * - the automatically generated salt used for the previous computation
+ * - the other parameters required to verify the password: opslimit and
+ *   memlimit
+ *
+ * `OPSLIMIT_INTERACTIVE` and `MEMLIMIT_INTERACTIVE` are safe baseline
+ * values to use for `opslimit` and `memlimit`.
+ *
+ * The function returns `Ok(hashed_password)` on success and `Err(())` if it
+ * didn't complete successfully
+ */
+pub fn pwhash(passwd: &[u8],
+              OpsLimit(opslimit): OpsLimit,
+              MemLimit(memlimit): MemLimit) -> Result<HashedPassword, ()> {
+    let mut out = HashedPassword([0; HASHEDPASSWORDBYTES]);
+    let res = unsafe {
+        let HashedPassword(ref mut str_) = out;
+        ffi::crypto_pwhash_scryptsalsa208sha256_str(
+            str_ as *mut [u8; HASHEDPASSWORDBYTES]
+                 as *mut [c_char; HASHEDPASSWORDBYTES],
+            passwd.as_ptr() as *const c_char,
+            passwd.len() as c_ulonglong,
+            opslimit as c_ulonglong,
+            memlimit as size_t)
+    };
+    if res == 0 {
+        Ok(out)
+    } else {
+        Err(())
+    }
+}
+
+/**
+ * `pwhash_verify()` verifies that the password `passwd` matches the
+ * `HashedPassword` `hp`. It returns `true` if the verification succeeds,
+ * and `false` on error.
+ */
+pub fn pwhash_verify(&HashedPassword(ref str_): &HashedPassword,
+                     passwd: &[u8]) -> bool {
+    let res = unsafe {
+        ffi::crypto_pwhash_scryptsalsa208sha256_str_verify(
+            str_ as *const [u8; HASHEDPASSWORDBYTES]
+                 as *const [c_char; HASHEDPASSWORDBYTES],
+            passwd.as_ptr() as *const c_char,
+            passwd.len() as c_ulonglong)
+    };
+    res == 0
+}
+
+/**
+ * `needs_rehash()` parses the stored hashed password `stored`, as produced by
+ * the `pwhash()` function of this module or of any other module of
+ * `crypto::pwhash`, and checks it against the policy given by `opslimit` and
+ * `memlimit`.
+ *
+ * `stored` may be followed by zero bytes, so the contents of any
+ * `HashedPassword` can be passed directly.
+ *
+ * The function returns `Ok(true)` if the password should be hashed again with
+ * this module, i.e. if it was hashed with a different algorithm, or with less
+ * memory (`N * r`) or fewer computations (`N * r * p`) than `pwhash()`
+ * chooses for `opslimit` and `memlimit`. It returns `Ok(false)` if the stored
+ * hash is at least as strong as the policy and `Err(())` if `stored` cannot
+ * be parsed.
+ */
+pub fn needs_rehash(stored: &[u8],
+                    OpsLimit(opslimit): OpsLimit,
+                    MemLimit(memlimit): MemLimit) -> Result<bool, ()> {
+    rehash::needs_rehash(stored,
+                         |p| rehash::scrypt_is_weaker(p, opslimit, memlimit))
+}
+
+/**
+ * `verify_and_upgrade()` verifies that the password `passwd` matches the
+ * stored hashed password `stored`, and hashes it again with this module
+ * using `opslimit` and `memlimit` if `needs_rehash()` says so.
+ *
+ * The function returns `Ok(Some(hashed_password))` if the password is correct
+ * and the stored hash has to be replaced with `hashed_password`, and
+ * `Ok(None)` if the password is correct and the stored hash is fine as is.
+ * It returns `Err(())` if the password doesn't match, if `stored` cannot be
+ * parsed or if computing the new hash didn't complete successfully.
+ */
+pub fn verify_and_upgrade(stored: &[u8],
+                          passwd: &[u8],
+                          opslimit: OpsLimit,
+                          memlimit: MemLimit)
+                          -> Result<Option<HashedPassword>, ()> {
+    let (OpsLimit(o), MemLimit(m)) = (opslimit, memlimit);
+    rehash::verify_and_upgrade(stored, passwd,
+                               |p| rehash::scrypt_is_weaker(p, o, m),
+                               || pwhash(passwd, opslimit, memlimit))
+}
+
+/**
+ * `scrypt()` computes the raw scrypt key derivation function of the password
+ * `passwd` and the salt `salt`, as specified in RFC 7914, and returns
+ * `out_len` bytes of output.
+ *
+ * Unlike `derive_key()`, which chooses the scrypt parameters from an
+ * `OpsLimit` and a `MemLimit`, `scrypt()` takes the CPU/memory cost `n`, the
+ * block size `r` and the parallelization parameter `p` directly. This is
+ * useful to interoperate with key files and formats that specify these
+ * parameters numerically, e.g. `n = 2^18`, `r = 8`, `p = 1`. The salt can
+ * have any length. scrypt requires about `128 * r * n` bytes of memory.
+ *
+ * The function returns `Err(())` if `n` isn't a power of 2 greater than 1,
+ * if `r` or `p` is zero, if `n` is too large for `r`
+ * (`n >= 2^(16 * r)`), if `r * p >= 2^30`, if `out_len` is zero or greater
+ * than `(2^32 - 1) * 32`, or if the computation didn't complete, usually
+ * because the operating system refused to allocate the amount of requested
+ * memory.
+ */
+pub fn scrypt(passwd: &[u8],
+              salt: &[u8],
+              n: u64,
+              r: u32,
+              p: u32,
+              out_len: usize) -> Result<Vec<u8>, ()> {
+    if n < 2 || n & (n - 1) != 0 || r == 0 || p == 0 {
+        return Err(())
+    }
+    if (r as u64) < 4 && n >= 1u64 << (16 * r as u64) {
+        return Err(())
+    }
+    if (r as u64) * (p as u64) >= 1u64 << 30 {
+        return Err(())
+    }
+    if out_len == 0 || out_len as u64 > 0xffffffffu64 * 32 {
+        return Err(())
+    }
+    let n_usize = n as usize;
+    if n_usize as u64 != n ||
+       n_usize.checked_mul(128)
+              .and_then(|m| m.checked_mul(r as usize)).is_none() {
+        return Err(())
+    }
+    let mut out: Vec<u8> = repeat(0u8).take(out_len).collect();
+    let res = unsafe {
+        ffi::crypto_pwhash_scryptsalsa208sha256_ll(passwd.as_ptr(),
+                                                   passwd.len() as size_t,
+                                                   salt.as_ptr(),
+                                                   salt.len() as size_t,
+                                                   n,
+                                                   r,
+                                                   p,
+                                                   out.as_mut_ptr(),
+                                                   out_len as size_t)
+    };
+    if res == 0 {
+        Ok(out)
+    } else {
+        Err(())
+    }
+}
+
+#[test]
+fn test_derive_key() {
+    let mut kb = [0u8; 32];
+    let salt = Salt([0x5f,0x7b,0x67,0xef,0x3b,0x9d,0x3a,0x5e
+                    ,0x37,0xf0,0xa1,0xc2,0xb1,0xd8,0xe3,0xf5
+                    ,0xc3,0xa4,0xb6,0xd7,0xe8,0xf9,0x0a,0x1b
+                    ,0x2c,0x3d,0x4e,0x5f,0x60,0x71,0x82,0x93]);
+    let pw = b"Correct Horse Battery Staple";
+    // computed with scrypt, N = 2^14, r = 8, p = 1
+    let key_expected = [0x31,0x1b,0xdc,0x61,0x23,0xb3,0x85,0x60
+                       ,0x56,0x15,0x41,0xef,0xc1,0xde,0x2c,0x76
+                       ,0xf4,0x76,0x94,0x06,0xc2,0xb7,0xea,0xe4
+                       ,0xdb,0x22,0xcf,0x6c,0xeb,0xdc,0xda,0xb6];
+    let key = derive_key(&mut kb, pw, &salt,
+                         OPSLIMIT_INTERACTIVE,
+                         MEMLIMIT_INTERACTIVE).unwrap();
+    assert!(key == &key_expected[..]);
+}
+
+#[test]
+fn test_scrypt_rfc7914_1() {
+    // RFC 7914, Section 12
+    let expected = [0x77,0xd6,0x57,0x62,0x38,0x65,0x7b,0x20
+                   ,0x3b,0x19,0xca,0x42,0xc1,0x8a,0x04,0x97
+                   ,0xf1,0x6b,0x48,0x44,0xe3,0x07,0x4a,0xe8
+                   ,0xdf,0xdf,0xfa,0x3f,0xed,0xe2,0x14,0x42
+                   ,0xfc,0xd0,0x06,0x9d,0xed,0x09,0x48,0xf8
+                   ,0x32,0x6a,0x75,0x3a,0x0f,0xc8,0x1f,0x17
+                   ,0xe8,0xd3,0xe0,0xfb,0x2e,0x0d,0x36,0x28
+                   ,0xcf,0x35,0xe2,0x0c,0x38,0xd1,0x89,0x06];
+    let out = scrypt(b"", b"", 16, 1, 1, 64).unwrap();
+    assert!(&out[..] == &expected[..]);
+}
+
+#[test]
+fn test_scrypt_rfc7914_2() {
+    // RFC 7914, Section 12
+    let expected = [0xfd,0xba,0xbe,0x1c,0x9d,0x34,0x72,0x00
+                   ,0x78,0x56,0xe7,0x19,0x0d,0x01,0xe9,0xfe
+                   ,0x7c,0x6a,0xd7,0xcb,0xc8,0x23,0x78,0x30
+                   ,0xe7,0x73,0x76,0x63,0x4b,0x37,0x31,0x62
+                   ,0x2e,0xaf,0x30,0xd9,0x2e,0x22,0xa3,0x88
+                   ,0x6f,0xf1,0x09,0x27,0x9d,0x98,0x30,0xda
+                   ,0xc7,0x27,0xaf,0xb9,0x4a,0x83,0xee,0x6d
+                   ,0x83,0x60,0xcb,0xdf,0xa2,0xcc,0x06,0x40];
+    let out = scrypt(b"password", b"NaCl", 1024, 8, 16, 64).unwrap();
+    assert!(&out[..] == &expected[..]);
+}
+
+#[test]
+fn test_scrypt_rfc7914_3() {
+    // RFC 7914, Section 12
+    let expected = [0x70,0x23,0xbd,0xcb,0x3a,0xfd,0x73,0x48
+                   ,0x46,0x1c,0x06,0xcd,0x81,0xfd,0x38,0xeb
+                   ,0xfd,0xa8,0xfb,0xba,0x90,0x4f,0x8e,0x3e
+                   ,0xa9,0xb5,0x43,0xf6,0x54,0x5d,0xa1,0xf2
+                   ,0xd5,0x43,0x29,0x55,0x61,0x3f,0x0f,0xcf
+                   ,0x62,0xd4,0x97,0x05,0x24,0x2a,0x9a,0xf9
+                   ,0xe6,0x1e,0x85,0xdc,0x0d,0x65,0x1e,0x40
+                   ,0xdf,0xcf,0x01,0x7b,0x45,0x57,0x58,0x87];
+    let out = scrypt(b"pleaseletmein", b"SodiumChloride",
+                     16384, 8, 1, 64).unwrap();
+    assert!(&out[..] == &expected[..]);
+}
+
+#[test]
+fn test_scrypt_invalid_params() {
+    let pw = b"password";
+    let salt = b"NaCl";
+    assert!(scrypt(pw, salt, 0, 8, 1, 64).is_err());
+    assert!(scrypt(pw, salt, 1, 8, 1, 64).is_err());
+    assert!(scrypt(pw, salt, 1000, 8, 1, 64).is_err());
+    assert!(scrypt(pw, salt, 1024, 0, 1, 64).is_err());
+    assert!(scrypt(pw, salt, 1024, 8, 0, 64).is_err());
+    assert!(scrypt(pw, salt, 1024, 8, 1 << 27, 64).is_err());
+    assert!(scrypt(pw, salt, 65536, 1, 1, 64).is_err());
+    assert!(scrypt(pw, salt, 1 << 63, 8, 1, 64).is_err());
+    assert!(scrypt(pw, salt, 1024, 8, 1, 0).is_err());
+    assert!(scrypt(pw, salt, 32768, 1, 1, 64).is_ok());
+}
+
+#[test]
+fn test_scrypt_derive_key() {
+    // derive_key() with OPSLIMIT_INTERACTIVE and MEMLIMIT_INTERACTIVE
+    // uses N = 2^14, r = 8, p = 1
+    let pw = b"Correct Horse Battery Staple";
+    let salt = gen_salt();
+    let mut kb = [0u8; 32];
+    let key = derive_key(&mut kb, pw, &salt,
+                         OPSLIMIT_INTERACTIVE,
+                         MEMLIMIT_INTERACTIVE).unwrap();
+    let out = scrypt(pw, &salt[..], 16384, 8, 1, 32).unwrap();
+    assert!(key == &out[..]);
+}
+
+#[test]
+fn test_pwhash_verify() {
+    use randombytes::randombytes;
+    for i in (0..32us) {
+        let pw = randombytes(i);
+        let pwh = pwhash(&pw, OPSLIMIT_INTERACTIVE, MEMLIMIT_INTERACTIVE).unwrap();
+        assert!(&pwh[..STRPREFIX.len()] == STRPREFIX.as_bytes());
+        assert!(pwhash_verify(&pwh, &pw));
+    }
+}
+
+#[test]
+fn test_pwhash_verify_tamper() {
+    use randombytes::randombytes;
+    for i in (0..16us) {
+        let mut pw = randombytes(i);
+        let pwh = pwhash(&pw, OPSLIMIT_INTERACTIVE, MEMLIMIT_INTERACTIVE).unwrap();
+        for j in (0..pw.len()) {
+            pw[j] ^= 0x20;
+            assert!(!pwhash_verify(&pwh, &pw));
+            pw[j] ^= 0x20;
+        }
+    }
+}
+
+#[test]
+fn test_pwhash_verify_known() {
+    // generated with crypto_pwhash_scryptsalsa208sha256_str() from libsodium
+    let s = b"$7$C6..../....eUbCxNYbVsvLPRB2q8mQ9QmxPkDBrK93onhzyE3AX08$tE9.zkjTJLzThzuJ6jFA4ltvWNARONSaMK/.iBKPtlD";
+    let mut str_ = [0; HASHEDPASSWORDBYTES];
+    for (d, &c) in str_.iter_mut().zip(s.iter()) {
+        *d = c;
+    }
+    let pwh = HashedPassword(str_);
+    assert!(pwhash_verify(&pwh, b"Correct Horse Battery Staple"));
+    assert!(!pwhash_verify(&pwh, b"Correct Horse Battery Stapler"));
+}
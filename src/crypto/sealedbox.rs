@@ -0,0 +1,30 @@
+/*!
+Anonymous public-key encryption (sealed boxes)
+
+# Security model
+Sealed boxes are designed to anonymously send messages to a recipient given
+its public key.
+
+Only the recipient can decrypt these messages, using its secret key. While
+the recipient can verify the integrity of the message, it cannot verify the
+identity of the sender.
+
+A message is encrypted using an ephemeral key pair, whose secret part is
+destroyed right after the encryption process. Without knowing the secret key
+used for a given message, the sender cannot decrypt its own message later.
+And without additional data, a message cannot be correlated with the identity
+of its sender.
+
+Unlike `crypto::asymmetricbox` the caller doesn't need a long-term key pair
+and doesn't have to choose or transmit a nonce.
+
+# Selected primitive
+`seal()` is `crypto_box_seal`. A new ephemeral key pair `(epk, esk)` is
+generated for each message, and `m` is encrypted with
+`crypto_box_curve25519xsalsa20poly1305` using `esk`, the recipient's public
+key `pk` and the nonce `blake2b(epk || pk)`. The ciphertext is `epk`
+followed by the box.
+*/
+pub use self::curve25519blake2bxsalsa20poly1305::*;
+#[path="curve25519blake2bxsalsa20poly1305.rs"]
+pub mod curve25519blake2bxsalsa20poly1305;
@@ -22,7 +22,33 @@ combination of Salsa20 and Poly1305 specified in
 
 This function is conjectured to meet the standard notions of privacy and
 authenticity.
+
+# Alternate primitives
+`crypto_secretbox_xchacha20poly1305` (in `secretbox::xchacha20poly1305`) has the
+same API and key and nonce sizes, but uses XChaCha20 instead of XSalsa20.
+
+# Detached mode
+`seal_detached()` encrypts a message in place and returns the authentication
+tag separately as a `Tag`, instead of prepending it to the ciphertext.
+`open_detached()` verifies the tag and decrypts the ciphertext in place.
+
+# In-place operation
+`seal_inplace()` and `open_inplace()` work on a `Vec<u8>` which holds the
+message or the ciphertext, so that large payloads can be processed without
+copying them into a new buffer.
+
+# Random-access encryption
+`secretbox::seekable` encrypts large blobs in independently authenticated
+blocks, so that arbitrary byte ranges can be decrypted and single blocks
+can be rewritten without processing the whole blob.
 */
 pub use self::xsalsa20poly1305::*;
+#[path="secretbox_macros.rs"]
+#[macro_use]
+mod secretbox_macros;
 #[path="xsalsa20poly1305.rs"]
 pub mod xsalsa20poly1305;
+#[path="xchacha20poly1305.rs"]
+pub mod xchacha20poly1305;
+#[path="secretbox_seekable.rs"]
+pub mod seekable;
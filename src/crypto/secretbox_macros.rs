@@ -0,0 +1,408 @@
+macro_rules! secretbox_module (($seal_name:ident,
+                                $open_name:ident,
+                                $seal_detached_name:ident,
+                                $open_detached_name:ident,
+                                $keybytes:expr,
+                                $noncebytes:expr,
+                                $macbytes:expr) => (
+
+use libc::c_ulonglong;
+use std::intrinsics::volatile_set_memory;
+use std::ops::{Index, Range, RangeFrom, RangeFull, RangeTo};
+use std::iter::repeat;
+use randombytes::randombytes_into;
+use crypto::verify::verify_16;
+
+pub const KEYBYTES: usize = $keybytes;
+pub const NONCEBYTES: usize = $noncebytes;
+pub const MACBYTES: usize = $macbytes;
+
+/**
+ * `Key` for symmetric authenticated encryption
+ *
+ * When a `Key` goes out of scope its contents
+ * will be zeroed out
+ */
+pub struct Key(pub [u8; KEYBYTES]);
+
+newtype_drop!(Key);
+newtype_clone!(Key);
+newtype_impl!(Key, KEYBYTES);
+
+/**
+ * `Nonce` for symmetric authenticated encryption
+ */
+#[derive(Copy)]
+pub struct Nonce(pub [u8; NONCEBYTES]);
+
+newtype_clone!(Nonce);
+newtype_impl!(Nonce, NONCEBYTES);
+
+/**
+ * Authentication `Tag` for the detached mode
+ *
+ * The tag implements the traits `PartialEq` and `Eq` using constant-time
+ * comparison functions. See `sodiumoxide::crypto::verify::verify_16`
+ */
+#[derive(Copy)]
+pub struct Tag(pub [u8; MACBYTES]);
+
+impl Eq for Tag {}
+
+impl PartialEq for Tag {
+    fn eq(&self, &Tag(other): &Tag) -> bool {
+        let &Tag(ref tag) = self;
+        verify_16(tag, &other)
+    }
+}
+
+newtype_clone!(Tag);
+newtype_impl!(Tag, MACBYTES);
+
+/**
+ * `gen_key()` randomly generates a secret key
+ *
+ * THREAD SAFETY: `gen_key()` is thread-safe provided that you have
+ * called `sodiumoxide::init()` once before using any other function
+ * from sodiumoxide.
+ */
+pub fn gen_key() -> Key {
+    let mut key = [0; KEYBYTES];
+    randombytes_into(&mut key);
+    Key(key)
+}
+
+/**
+ * `gen_nonce()` randomly generates a nonce
+ *
+ * THREAD SAFETY: `gen_key()` is thread-safe provided that you have
+ * called `sodiumoxide::init()` once before using any other function
+ * from sodiumoxide.
+ */
+pub fn gen_nonce() -> Nonce {
+    let mut nonce = [0; NONCEBYTES];
+    randombytes_into(&mut nonce);
+    Nonce(nonce)
+}
+
+/**
+ * `seal()` encrypts and authenticates a message `m` using a secret key `k` and a
+ * nonce `n`.  It returns a ciphertext `c`.
+ */
+pub fn seal(m: &[u8],
+            &Nonce(ref n): &Nonce,
+            &Key(ref k): &Key) -> Vec<u8> {
+    let mut c: Vec<u8> = repeat(0u8).take(m.len() + MACBYTES).collect();
+    unsafe {
+        $seal_name(c.as_mut_ptr(),
+                   m.as_ptr(),
+                   m.len() as c_ulonglong,
+                   n,
+                   k);
+    }
+    c
+}
+
+/**
+ * `open()` verifies and decrypts a ciphertext `c` using a secret key `k` and a nonce `n`.
+ * It returns a plaintext `Some(m)`.
+ * If the ciphertext fails verification, `open()` returns `None`.
+ */
+pub fn open(c: &[u8],
+            &Nonce(ref n): &Nonce,
+            &Key(ref k): &Key) -> Option<Vec<u8>> {
+    if c.len() < MACBYTES {
+        return None
+    }
+    let mut m: Vec<u8> = repeat(0u8).take(c.len() - MACBYTES).collect();
+    let ret = unsafe {
+        $open_name(m.as_mut_ptr(),
+                   c.as_ptr(),
+                   c.len() as c_ulonglong,
+                   n,
+                   k)
+    };
+    if ret == 0 {
+        Some(m)
+    } else {
+        None
+    }
+}
+
+/**
+ * `seal_inplace()` encrypts and authenticates the message in `buf` using a secret
+ * key `k` and a nonce `n`. After `seal_inplace()` returns `buf` contains the
+ * ciphertext, which is `MACBYTES` longer than the message. No new buffer is
+ * allocated if `buf` has enough spare capacity.
+ */
+pub fn seal_inplace(buf: &mut Vec<u8>,
+                    &Nonce(ref n): &Nonce,
+                    &Key(ref k): &Key) {
+    let mlen = buf.len();
+    buf.extend(repeat(0u8).take(MACBYTES));
+    unsafe {
+        $seal_name(buf.as_mut_ptr(),
+                   buf.as_ptr(),
+                   mlen as c_ulonglong,
+                   n,
+                   k);
+    }
+}
+
+/**
+ * `open_inplace()` verifies and decrypts the ciphertext in `buf` using a secret
+ * key `k` and a nonce `n`. If `open_inplace()` returns `Ok(())` `buf` contains
+ * the plaintext.
+ * If the ciphertext fails verification, `open_inplace()` returns `Err(())` and
+ * leaves `buf` unchanged.
+ */
+pub fn open_inplace(buf: &mut Vec<u8>,
+                    &Nonce(ref n): &Nonce,
+                    &Key(ref k): &Key) -> Result<(), ()> {
+    let clen = buf.len();
+    if clen < MACBYTES {
+        return Err(())
+    }
+    let ret = unsafe {
+        $open_name(buf.as_mut_ptr(),
+                   buf.as_ptr(),
+                   clen as c_ulonglong,
+                   n,
+                   k)
+    };
+    if ret == 0 {
+        buf.truncate(clen - MACBYTES);
+        Ok(())
+    } else {
+        Err(())
+    }
+}
+
+/**
+ * `seal_detached()` encrypts and authenticates a message `m` using a secret key `k`
+ * and a nonce `n`. `m` is encrypted in place, so after `seal_detached()` returns it
+ * will contain the ciphertext. The authentication tag is returned separately.
+ */
+pub fn seal_detached(m: &mut [u8],
+                     &Nonce(ref n): &Nonce,
+                     &Key(ref k): &Key) -> Tag {
+    let mut tag = [0u8; MACBYTES];
+    unsafe {
+        $seal_detached_name(m.as_mut_ptr(),
+                            &mut tag,
+                            m.as_ptr(),
+                            m.len() as c_ulonglong,
+                            n,
+                            k);
+    }
+    Tag(tag)
+}
+
+/**
+ * `open_detached()` verifies and decrypts a ciphertext `c` using a secret key `k`,
+ * a nonce `n` and the authentication tag `tag`. `c` is decrypted in place, so if
+ * `open_detached()` returns `Ok(())` it will contain the plaintext.
+ * If the ciphertext fails verification, `open_detached()` returns `Err(())` and
+ * leaves `c` unchanged.
+ */
+pub fn open_detached(c: &mut [u8],
+                     &Tag(ref tag): &Tag,
+                     &Nonce(ref n): &Nonce,
+                     &Key(ref k): &Key) -> Result<(), ()> {
+    let ret = unsafe {
+        $open_detached_name(c.as_mut_ptr(),
+                            c.as_ptr(),
+                            tag,
+                            c.len() as c_ulonglong,
+                            n,
+                            k)
+    };
+    if ret == 0 {
+        Ok(())
+    } else {
+        Err(())
+    }
+}
+
+#[test]
+fn test_seal_open() {
+    use randombytes::randombytes;
+    for i in (0..256us) {
+        let k = gen_key();
+        let m = randombytes(i);
+        let n = gen_nonce();
+        let c = seal(&m, &n, &k);
+        let opened = open(&c, &n, &k);
+        assert!(Some(m) == opened);
+    }
+}
+
+#[test]
+fn test_seal_open_tamper() {
+    use randombytes::randombytes;
+    for i in (0..32us) {
+        let k = gen_key();
+        let m = randombytes(i);
+        let n = gen_nonce();
+        let mut cv = seal(&m, &n, &k);
+        let c = cv.as_mut_slice();
+        for i in (0..c.len()) {
+            c[i] ^= 0x20;
+            assert!(None == open(c, &n, &k));
+            c[i] ^= 0x20;
+        }
+    }
+}
+
+#[test]
+fn test_seal_open_inplace() {
+    use randombytes::randombytes;
+    for i in (0..256us) {
+        let k = gen_key();
+        let m = randombytes(i);
+        let n = gen_nonce();
+        let mut buf = m.clone();
+        seal_inplace(&mut buf, &n, &k);
+        assert!(buf == seal(&m, &n, &k));
+        assert!(open_inplace(&mut buf, &n, &k).is_ok());
+        assert!(buf == m);
+    }
+}
+
+#[test]
+fn test_seal_open_inplace_tamper() {
+    use randombytes::randombytes;
+    for i in (0..32us) {
+        let k = gen_key();
+        let n = gen_nonce();
+        let mut c = randombytes(i);
+        seal_inplace(&mut c, &n, &k);
+        for j in (0..c.len()) {
+            let mut tampered = c.clone();
+            tampered[j] ^= 0x20;
+            let expected = tampered.clone();
+            assert!(open_inplace(&mut tampered, &n, &k).is_err());
+            assert!(tampered == expected);
+        }
+    }
+}
+
+#[test]
+fn test_open_inplace_short() {
+    let k = gen_key();
+    let n = gen_nonce();
+    for i in (0..MACBYTES) {
+        let mut buf: Vec<u8> = repeat(0u8).take(i).collect();
+        assert!(open_inplace(&mut buf, &n, &k).is_err());
+    }
+}
+
+#[test]
+fn test_seal_open_detached() {
+    use randombytes::randombytes;
+    for i in (0..256us) {
+        let k = gen_key();
+        let m = randombytes(i);
+        let n = gen_nonce();
+        let mut buf = m.clone();
+        let tag = seal_detached(buf.as_mut_slice(), &n, &k);
+        assert!(open_detached(buf.as_mut_slice(), &tag, &n, &k).is_ok());
+        assert!(m == buf);
+    }
+}
+
+#[test]
+fn test_seal_detached_same() {
+    use randombytes::randombytes;
+    for i in (0..256us) {
+        let k = gen_key();
+        let m = randombytes(i);
+        let n = gen_nonce();
+        let c = seal(&m, &n, &k);
+        let mut buf = m.clone();
+        let Tag(tag) = seal_detached(buf.as_mut_slice(), &n, &k);
+        assert!(&c[..MACBYTES] == &tag[..]);
+        assert!(&c[MACBYTES..] == &buf[..]);
+    }
+}
+
+#[test]
+fn test_seal_open_detached_tamper() {
+    use randombytes::randombytes;
+    for i in (0..32us) {
+        let k = gen_key();
+        let n = gen_nonce();
+        let mut c = randombytes(i);
+        let Tag(mut tagbuf) = seal_detached(c.as_mut_slice(), &n, &k);
+        for j in (0..c.len()) {
+            let mut tampered = c.clone();
+            tampered[j] ^= 0x20;
+            assert!(open_detached(tampered.as_mut_slice(), &Tag(tagbuf), &n, &k).is_err());
+        }
+        for j in (0..tagbuf.len()) {
+            let mut cv = c.clone();
+            tagbuf[j] ^= 0x20;
+            assert!(open_detached(cv.as_mut_slice(), &Tag(tagbuf), &n, &k).is_err());
+            assert!(cv == c);
+            tagbuf[j] ^= 0x20;
+        }
+    }
+}
+
+#[cfg(test)]
+mod bench {
+    extern crate test;
+    use randombytes::randombytes;
+    use super::*;
+
+    const BENCH_SIZES: [usize; 14] = [0, 1, 2, 4, 8, 16, 32, 64,
+                                      128, 256, 512, 1024, 2048, 4096];
+
+    #[bench]
+    fn bench_seal_open(b: &mut test::Bencher) {
+        let k = gen_key();
+        let n = gen_nonce();
+        let ms: Vec<Vec<u8>> = BENCH_SIZES.iter().map(|s| {
+            randombytes(*s)
+        }).collect();
+        b.iter(|| {
+            for m in ms.iter() {
+                open(&seal(&m, &n, &k), &n, &k).unwrap();
+            }
+        });
+    }
+
+    #[bench]
+    fn bench_seal_open_inplace(b: &mut test::Bencher) {
+        let k = gen_key();
+        let n = gen_nonce();
+        let mut ms: Vec<Vec<u8>> = BENCH_SIZES.iter().map(|s| {
+            let mut m = Vec::with_capacity(*s + MACBYTES);
+            m.push_all(&randombytes(*s));
+            m
+        }).collect();
+        b.iter(|| {
+            for m in ms.iter_mut() {
+                seal_inplace(m, &n, &k);
+                open_inplace(m, &n, &k).unwrap();
+            }
+        });
+    }
+
+    #[bench]
+    fn bench_seal_open_detached(b: &mut test::Bencher) {
+        let k = gen_key();
+        let n = gen_nonce();
+        let mut ms: Vec<Vec<u8>> = BENCH_SIZES.iter().map(|s| {
+            randombytes(*s)
+        }).collect();
+        b.iter(|| {
+            for m in ms.iter_mut() {
+                let tag = seal_detached(m.as_mut_slice(), &n, &k);
+                open_detached(m.as_mut_slice(), &tag, &n, &k).unwrap();
+            }
+        });
+    }
+}
+
+));
@@ -0,0 +1,516 @@
+/*!
+Random-access encrypted blobs
+
+`SeekableEncryptor` splits data into fixed-size blocks and encrypts every
+block on its own with `crypto::secretbox::xsalsa20poly1305`.
+`SeekableDecryptor` implements `Read` and `Seek`, so arbitrary byte ranges
+can be decrypted without reading the blob from the start. `rewrite_block()`
+replaces the contents of a single block in place.
+
+# Format
+All integers are big-endian.
+
+---------------------------------------------------------------
+|offset|length              |content                          |
+|------|--------------------|---------------------------------|
+|0     |4                   |magic bytes `MAGIC`              |
+|4     |1                   |algorithm id, 1 for xsalsa20poly1305|
+|5     |4                   |block size `n`                   |
+|9     |`n + BLOCKOVERHEAD` |block 0                          |
+|...   |...                 |...                              |
+|      |`BLOCKOVERHEAD` to  |last block                       |
+|      |`n + BLOCKOVERHEAD` |                                 |
+---------------------------------------------------------------
+
+Every block but the last one holds exactly `n` bytes of plaintext, the last
+block holds at most `n` bytes. A block is stored as 16 random bytes followed
+by the secretbox of its plaintext. The nonce of the secretbox consists of
+the 16 random bytes, the block index (7 bytes) and a flag (1 byte) that is
+set for the last block only. Blocks therefore can't be moved to another
+position, and truncating the blob is detected.
+
+Every write of a block uses fresh random bytes, so rewriting a block never
+reuses a nonce. Note that an attacker who has seen an older version of the
+blob can replace a block with its older version without being detected.
+*/
+use std::io;
+use std::io::{Read, Write, Seek, SeekFrom};
+use super::xsalsa20poly1305::{Key, Nonce, seal, open, MACBYTES, NONCEBYTES};
+use randombytes::randombytes_into;
+use crypto::io_util::{invalid, read_full};
+use std::iter::repeat;
+
+pub const MAGIC: [u8; 4] = [0x73, 0x6f, 0x53, 0x42]; // "soSB"
+pub const ALGORITHM_XSALSA20POLY1305: u8 = 1;
+pub const DEFAULT_BLOCK_SIZE: usize = 65536;
+pub const MAX_BLOCK_SIZE: usize = 16777216;
+pub const HEADERBYTES: usize = 9;
+pub const BLOCKOVERHEAD: usize = RANDBYTES + MACBYTES;
+
+const RANDBYTES: usize = 16;
+
+fn block_nonce(rand: &[u8], index: u64, last: bool) -> Nonce {
+    let mut n = [0; NONCEBYTES];
+    for (ni, &ri) in n.iter_mut().zip(rand.iter()) {
+        *ni = ri;
+    }
+    for i in (0..7us) {
+        n[RANDBYTES + i] = (index >> (8 * (6 - i))) as u8;
+    }
+    n[NONCEBYTES - 1] = if last { 1 } else { 0 };
+    Nonce(n)
+}
+
+fn seal_block(m: &[u8], index: u64, last: bool, k: &Key) -> Vec<u8> {
+    let mut rand = [0; RANDBYTES];
+    randombytes_into(&mut rand);
+    let mut b = Vec::with_capacity(m.len() + BLOCKOVERHEAD);
+    b.push_all(&rand);
+    b.push_all(&seal(m, &block_nonce(&rand, index, last), k));
+    b
+}
+
+fn open_block(b: &[u8], index: u64, last: bool, k: &Key) -> Option<Vec<u8>> {
+    if b.len() < BLOCKOVERHEAD {
+        return None
+    }
+    open(&b[RANDBYTES..], &block_nonce(&b[..RANDBYTES], index, last), k)
+}
+
+/* Position of the blocks in a blob, computed from the header and the size
+   of the blob */
+struct Layout {
+    block_size: usize,
+    nblocks: u64,
+    len: u64,
+}
+
+impl Layout {
+    fn read<F: Read + Seek>(f: &mut F) -> io::Result<Layout> {
+        let mut header = [0; HEADERBYTES];
+        try!(f.seek(SeekFrom::Start(0)));
+        if try!(read_full(f, &mut header)) < HEADERBYTES {
+            return Err(invalid("blob header truncated"))
+        }
+        if &header[..4] != &MAGIC[..] {
+            return Err(invalid("not an encrypted blob"))
+        }
+        if header[4] != ALGORITHM_XSALSA20POLY1305 {
+            return Err(invalid("unsupported algorithm"))
+        }
+        let block_size = ((header[5] as usize) << 24) |
+                         ((header[6] as usize) << 16) |
+                         ((header[7] as usize) << 8) |
+                         (header[8] as usize);
+        if block_size == 0 || block_size > MAX_BLOCK_SIZE {
+            return Err(invalid("invalid block size"))
+        }
+        let size = try!(f.seek(SeekFrom::End(0)));
+        let stored = (block_size + BLOCKOVERHEAD) as u64;
+        let data = size - HEADERBYTES as u64;
+        let nblocks = (data + stored - 1) / stored;
+        if nblocks == 0 || data - (nblocks - 1) * stored < BLOCKOVERHEAD as u64 {
+            return Err(invalid("blob truncated"))
+        }
+        Ok(Layout {
+            block_size: block_size,
+            nblocks: nblocks,
+            len: data - nblocks * BLOCKOVERHEAD as u64,
+        })
+    }
+
+    fn block_offset(&self, index: u64) -> u64 {
+        HEADERBYTES as u64 + index * (self.block_size + BLOCKOVERHEAD) as u64
+    }
+
+    fn block_len(&self, index: u64) -> usize {
+        if index + 1 < self.nblocks {
+            self.block_size
+        } else {
+            (self.len - index * self.block_size as u64) as usize
+        }
+    }
+}
+
+/**
+ * `SeekableEncryptor` encrypts everything written to it into a blob that
+ * can be read with `SeekableDecryptor`.
+ *
+ * `finish()` has to be called after the last write, otherwise the last
+ * block is missing and the blob is rejected as truncated.
+ */
+pub struct SeekableEncryptor<W: Write> {
+    inner: W,
+    key: Key,
+    block_size: usize,
+    buf: Vec<u8>,
+    index: u64,
+}
+
+impl<W: Write> SeekableEncryptor<W> {
+    /**
+     * `new()` writes the header of a new blob, encrypted with the secret key
+     * `k`, to `inner` and uses `DEFAULT_BLOCK_SIZE`.
+     */
+    pub fn new(inner: W, k: &Key) -> io::Result<SeekableEncryptor<W>> {
+        SeekableEncryptor::with_block_size(inner, k, DEFAULT_BLOCK_SIZE)
+    }
+
+    /**
+     * `with_block_size()` is like `new()`, but splits the plaintext into
+     * blocks of `block_size` bytes, which has to be between 1 and
+     * `MAX_BLOCK_SIZE`.
+     */
+    pub fn with_block_size(mut inner: W,
+                           k: &Key,
+                           block_size: usize) -> io::Result<SeekableEncryptor<W>> {
+        if block_size == 0 || block_size > MAX_BLOCK_SIZE {
+            return Err(invalid("invalid block size"))
+        }
+        let header = [MAGIC[0], MAGIC[1], MAGIC[2], MAGIC[3],
+                      ALGORITHM_XSALSA20POLY1305,
+                      (block_size >> 24) as u8, (block_size >> 16) as u8,
+                      (block_size >> 8) as u8, block_size as u8];
+        try!(inner.write_all(&header));
+        Ok(SeekableEncryptor {
+            inner: inner,
+            key: k.clone(),
+            block_size: block_size,
+            buf: Vec::with_capacity(block_size),
+            index: 0,
+        })
+    }
+
+    /**
+     * `finish()` encrypts the remaining buffered data as the last block,
+     * flushes the inner writer and returns it.
+     */
+    pub fn finish(mut self) -> io::Result<W> {
+        let b = seal_block(&self.buf, self.index, true, &self.key);
+        try!(self.inner.write_all(&b));
+        try!(self.inner.flush());
+        let SeekableEncryptor { inner, .. } = self;
+        Ok(inner)
+    }
+}
+
+impl<W: Write> Write for SeekableEncryptor<W> {
+    fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+        // a full block is only written once more data follows, because the
+        // last block has to be marked as such
+        if self.buf.len() == self.block_size && data.len() > 0 {
+            let b = seal_block(&self.buf, self.index, false, &self.key);
+            try!(self.inner.write_all(&b));
+            self.buf.clear();
+            self.index += 1;
+        }
+        let n = if data.len() > self.block_size - self.buf.len() {
+            self.block_size - self.buf.len()
+        } else {
+            data.len()
+        };
+        self.buf.push_all(&data[..n]);
+        Ok(n)
+    }
+
+    /**
+     * `flush()` flushes the inner writer. The data of the current block stays
+     * buffered until the block is complete or `finish()` is called.
+     */
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/**
+ * `SeekableDecryptor` decrypts a blob written by `SeekableEncryptor`.
+ * Only the blocks that are actually read get decrypted.
+ */
+pub struct SeekableDecryptor<R: Read + Seek> {
+    inner: R,
+    key: Key,
+    layout: Layout,
+    pos: u64,
+    block: Vec<u8>,
+    block_index: Option<u64>,
+}
+
+impl<R: Read + Seek> SeekableDecryptor<R> {
+    /**
+     * `new()` reads and checks the header of the blob `inner` and prepares
+     * decrypting it with the secret key `k`.
+     *
+     * The last block is decrypted right away, so a wrong key or a truncated
+     * blob is detected even if the blob is empty and nothing is ever read.
+     */
+    pub fn new(mut inner: R, k: &Key) -> io::Result<SeekableDecryptor<R>> {
+        let layout = try!(Layout::read(&mut inner));
+        let last = layout.nblocks - 1;
+        let mut d = SeekableDecryptor {
+            inner: inner,
+            key: k.clone(),
+            layout: layout,
+            pos: 0,
+            block: Vec::new(),
+            block_index: None,
+        };
+        try!(d.load_block(last));
+        Ok(d)
+    }
+
+    /**
+     * `len()` returns the length of the decrypted blob in bytes
+     */
+    pub fn len(&self) -> u64 {
+        self.layout.len
+    }
+
+    /**
+     * `into_inner()` returns the inner reader
+     */
+    pub fn into_inner(self) -> R {
+        let SeekableDecryptor { inner, .. } = self;
+        inner
+    }
+
+    fn load_block(&mut self, index: u64) -> io::Result<()> {
+        if self.block_index == Some(index) {
+            return Ok(())
+        }
+        self.block_index = None;
+        let stored_len = self.layout.block_len(index) + BLOCKOVERHEAD;
+        let mut b: Vec<u8> = repeat(0u8).take(stored_len).collect();
+        try!(self.inner.seek(SeekFrom::Start(self.layout.block_offset(index))));
+        if try!(read_full(&mut self.inner, &mut b)) < stored_len {
+            return Err(invalid("blob truncated"))
+        }
+        let last = index + 1 == self.layout.nblocks;
+        match open_block(&b, index, last, &self.key) {
+            Some(m) => {
+                self.block = m;
+                self.block_index = Some(index);
+                Ok(())
+            }
+            None => Err(invalid("block failed verification"))
+        }
+    }
+}
+
+impl<R: Read + Seek> Read for SeekableDecryptor<R> {
+    fn read(&mut self, out: &mut [u8]) -> io::Result<usize> {
+        if self.pos >= self.layout.len || out.len() == 0 {
+            return Ok(0)
+        }
+        let index = self.pos / self.layout.block_size as u64;
+        try!(self.load_block(index));
+        let offset = (self.pos - index * self.layout.block_size as u64) as usize;
+        let mut n = 0;
+        for (o, b) in out.iter_mut().zip(self.block[offset..].iter()) {
+            *o = *b;
+            n += 1;
+        }
+        self.pos += n as u64;
+        Ok(n)
+    }
+}
+
+impl<R: Read + Seek> Seek for SeekableDecryptor<R> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let (base, offset) = match pos {
+            SeekFrom::Start(p) => {
+                self.pos = p;
+                return Ok(p)
+            }
+            SeekFrom::End(o) => (self.layout.len, o),
+            SeekFrom::Current(o) => (self.pos, o),
+        };
+        let pos = if offset < 0 {
+            offset.checked_neg().and_then(|o| base.checked_sub(o as u64))
+        } else {
+            base.checked_add(offset as u64)
+        };
+        match pos {
+            Some(p) => {
+                self.pos = p;
+                Ok(p)
+            }
+            None => Err(invalid("seek position out of range"))
+        }
+    }
+}
+
+/**
+ * `rewrite_block()` replaces the plaintext of block `index` of the blob `f`
+ * with `data`, encrypted with the secret key `k`.
+ *
+ * `data` must have the same length as the current plaintext of the block,
+ * i.e. the block size for all blocks but the last one.
+ */
+pub fn rewrite_block<F: Read + Write + Seek>(f: &mut F,
+                                             k: &Key,
+                                             index: u64,
+                                             data: &[u8]) -> io::Result<()> {
+    let layout = try!(Layout::read(f));
+    if index >= layout.nblocks {
+        return Err(invalid("block index out of range"))
+    }
+    if data.len() != layout.block_len(index) {
+        return Err(invalid("wrong block length"))
+    }
+    let last = index + 1 == layout.nblocks;
+    let b = seal_block(data, index, last, k);
+    try!(f.seek(SeekFrom::Start(layout.block_offset(index))));
+    f.write_all(&b)
+}
+
+#[cfg(test)]
+fn encrypt(m: &[u8], k: &Key, block_size: usize) -> Vec<u8> {
+    let mut w = SeekableEncryptor::with_block_size(Vec::new(), k, block_size).unwrap();
+    w.write_all(m).unwrap();
+    w.finish().unwrap()
+}
+
+#[cfg(test)]
+fn decrypt(c: Vec<u8>, k: &Key) -> io::Result<Vec<u8>> {
+    use std::io::Cursor;
+    use crypto::io_util::read_all;
+    read_all(SeekableDecryptor::new(Cursor::new(c), k))
+}
+
+#[test]
+fn test_encrypt_decrypt() {
+    use crypto::io_util::check_round_trip;
+    use super::xsalsa20poly1305::gen_key;
+    check_round_trip(HEADERBYTES, BLOCKOVERHEAD, |m, block_size| {
+        let k = gen_key();
+        let c = encrypt(m, &k, block_size);
+        let m2 = decrypt(c.clone(), &k).unwrap();
+        (c, m2)
+    });
+}
+
+#[test]
+fn test_random_seek() {
+    use randombytes::randombytes;
+    use std::io::Cursor;
+    use super::xsalsa20poly1305::gen_key;
+    let k = gen_key();
+    let len = 1000;
+    let m = randombytes(len);
+    let c = encrypt(&m, &k, 64);
+    let mut r = SeekableDecryptor::new(Cursor::new(c), &k).unwrap();
+    assert!(r.len() == len as u64);
+    for _ in (0..500us) {
+        let rnd = randombytes(4);
+        let start = ((rnd[0] as usize) << 8 | rnd[1] as usize) % (len + 10);
+        let count = ((rnd[2] as usize) << 8 | rnd[3] as usize) % 200;
+        let pos = match rnd[0] % 3 {
+            0 => r.seek(SeekFrom::Start(start as u64)).unwrap(),
+            1 => r.seek(SeekFrom::End(start as i64 - len as i64)).unwrap(),
+            _ => {
+                let cur = r.seek(SeekFrom::Current(0)).unwrap() as i64;
+                r.seek(SeekFrom::Current(start as i64 - cur)).unwrap()
+            }
+        };
+        assert!(pos == start as u64);
+        let mut buf: Vec<u8> = repeat(0u8).take(count).collect();
+        let n = read_full(&mut r, &mut buf).unwrap();
+        let end = if start + count > len { len } else { start + count };
+        let expected = if start < len { &m[start..end] } else { &m[..0] };
+        assert!(&buf[..n] == expected);
+    }
+    assert!(r.seek(SeekFrom::End(-(len as i64) - 1)).is_err());
+}
+
+#[test]
+fn test_tamper_and_truncation() {
+    use randombytes::randombytes;
+    use super::xsalsa20poly1305::gen_key;
+    let k = gen_key();
+    let block_size = 16;
+    let m = randombytes(3 * block_size + 5);
+    let mut c = encrypt(&m, &k, block_size);
+    for i in (0..c.len()) {
+        c[i] ^= 0x20;
+        assert!(decrypt(c.clone(), &k).is_err());
+        c[i] ^= 0x20;
+    }
+    for i in (0..c.len()) {
+        assert!(decrypt(c[..i].to_vec(), &k).is_err());
+    }
+    // swapping two blocks is detected
+    let stored = block_size + BLOCKOVERHEAD;
+    let mut swapped = c[..HEADERBYTES].to_vec();
+    swapped.push_all(&c[HEADERBYTES + stored..HEADERBYTES + 2 * stored]);
+    swapped.push_all(&c[HEADERBYTES..HEADERBYTES + stored]);
+    swapped.push_all(&c[HEADERBYTES + 2 * stored..]);
+    assert!(decrypt(swapped, &k).is_err());
+    assert!(decrypt(c.clone(), &gen_key()).is_err());
+    assert!(decrypt(c, &k).unwrap() == m);
+
+    // an empty blob is a single, empty last block
+    let mut c = encrypt(&[], &k, block_size);
+    assert!(c.len() == HEADERBYTES + BLOCKOVERHEAD);
+    for i in (0..c.len()) {
+        c[i] ^= 0x20;
+        assert!(decrypt(c.clone(), &k).is_err());
+        c[i] ^= 0x20;
+    }
+    for i in (0..c.len()) {
+        assert!(decrypt(c[..i].to_vec(), &k).is_err());
+    }
+    let mut forged = c[..HEADERBYTES].to_vec();
+    forged.push_all(&randombytes(BLOCKOVERHEAD));
+    assert!(decrypt(forged, &k).is_err());
+    assert!(decrypt(c.clone(), &gen_key()).is_err());
+    assert!(decrypt(c, &k).unwrap() == vec![]);
+}
+
+#[test]
+fn test_seek_out_of_range() {
+    use std::io::Cursor;
+    use std::{i64, u64};
+    use super::xsalsa20poly1305::gen_key;
+    let k = gen_key();
+    let c = encrypt(&[0; 100], &k, 64);
+    let mut r = SeekableDecryptor::new(Cursor::new(c), &k).unwrap();
+    assert!(r.seek(SeekFrom::End(-101)).is_err());
+    assert!(r.seek(SeekFrom::End(i64::MIN)).is_err());
+    assert!(r.seek(SeekFrom::Current(i64::MIN)).is_err());
+    assert!(r.seek(SeekFrom::Start(u64::MAX)).unwrap() == u64::MAX);
+    assert!(r.seek(SeekFrom::Current(1)).is_err());
+    assert!(r.seek(SeekFrom::End(-100)).unwrap() == 0);
+}
+
+#[test]
+fn test_rewrite_block() {
+    use randombytes::randombytes;
+    use std::io::Cursor;
+    use super::xsalsa20poly1305::gen_key;
+    let k = gen_key();
+    let block_size = 32;
+    let mut m = randombytes(4 * block_size + 10);
+    let c = encrypt(&m, &k, block_size);
+    let mut f = Cursor::new(c.clone());
+    let new_block = randombytes(block_size);
+    rewrite_block(&mut f, &k, 2, &new_block).unwrap();
+    for (mi, &bi) in m[2 * block_size..3 * block_size].iter_mut().zip(new_block.iter()) {
+        *mi = bi;
+    }
+    let new_last = randombytes(10);
+    rewrite_block(&mut f, &k, 4, &new_last).unwrap();
+    for (mi, &bi) in m[4 * block_size..].iter_mut().zip(new_last.iter()) {
+        *mi = bi;
+    }
+    let c2 = f.into_inner();
+    assert!(c2.len() == c.len());
+    // the rewritten block uses a fresh nonce
+    let off = HEADERBYTES + 2 * (block_size + BLOCKOVERHEAD);
+    assert!(&c2[off..off + RANDBYTES] != &c[off..off + RANDBYTES]);
+    assert!(decrypt(c2.clone(), &k).unwrap() == m);
+
+    let mut f = Cursor::new(c2);
+    assert!(rewrite_block(&mut f, &k, 5, &new_last).is_err());
+    assert!(rewrite_block(&mut f, &k, 4, &new_block).is_err());
+    assert!(rewrite_block(&mut f, &k, 1, &new_last).is_err());
+}
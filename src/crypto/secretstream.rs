@@ -0,0 +1,42 @@
+/*!
+Authenticated encryption of message streams
+
+# Security model
+`Push` encrypts a sequence of messages, or a single message split into an
+arbitrary number of chunks, using a secret key. `Pull` decrypts the sequence
+and guarantees that the chunks are neither modified, removed, reordered nor
+duplicated. Each chunk can carry optional associated data, which is
+authenticated but not encrypted.
+
+Every chunk is encrypted together with a `Tag`:
+
+- `Tag::Message` marks a regular chunk.
+- `Tag::Push` marks the end of a set of chunks that belong together, e.g. a
+  single message that has been split into several chunks.
+- `Tag::Rekey` derives a new key for the following chunks and forgets the old
+  one.
+- `Tag::Final` marks the end of the stream.
+
+Truncation of the stream can be detected by checking that the last chunk
+has been pulled with `Tag::Final`, see `Pull::is_finalized()`.
+
+Unlike `crypto::secretbox`, no nonces have to be managed by the caller: a
+random `Header` is generated by `Push::init()` and has to be passed to
+`Pull::init()`. The header does not need to be secret. The key is
+automatically rekeyed before the internal counter wraps around.
+
+# Selected primitive
+`Push` and `Pull` are `crypto_secretstream_xchacha20poly1305`, which combines
+XChaCha20 and Poly1305 and is wire-compatible with libsodium.
+
+# Encrypting `Read` and `Write` adapters
+`EncryptWriter` and `DecryptReader` (in `secretstream::encrypted_io`) wrap
+any `Write` and `Read` and encrypt and decrypt everything passing through
+them, using a self-describing chunked format that detects truncation.
+*/
+pub use self::xchacha20poly1305::*;
+#[path="secretstream_xchacha20poly1305.rs"]
+pub mod xchacha20poly1305;
+#[path="secretstream_io.rs"]
+pub mod encrypted_io;
+pub use self::encrypted_io::{EncryptWriter, DecryptReader};
@@ -0,0 +1,318 @@
+/*!
+Encrypting `Read` and `Write` adapters
+
+`EncryptWriter` turns any byte stream into a self-describing chunked
+ciphertext, and `DecryptReader` decrypts it again. Both are built on
+`crypto::secretstream::xchacha20poly1305`.
+
+# Format
+All integers are big-endian.
+
+---------------------------------------------------------------
+|offset|length         |content                               |
+|------|---------------|--------------------------------------|
+|0     |4              |magic bytes `MAGIC`                   |
+|4     |1              |algorithm id, 1 for xchacha20poly1305 |
+|5     |4              |chunk size `n`                        |
+|9     |24             |secretstream `Header`                 |
+|33    |`n + ABYTES`   |first chunk                           |
+|...   |...            |...                                   |
+|      |`ABYTES` to    |final chunk                           |
+|      |`n + ABYTES`   |                                      |
+---------------------------------------------------------------
+
+Every chunk but the last one holds exactly `n` bytes of plaintext and is
+pushed with `Tag::Message`. The last chunk holds at most `n` bytes (it may be
+empty) and is pushed with `Tag::Final`. The first 9 bytes of the format are
+passed as associated data with every chunk.
+
+`DecryptReader` returns an error if a chunk fails verification, if the
+stream ends before the final chunk (truncation) or if data follows the
+final chunk.
+*/
+use std::io;
+use std::io::{Read, Write};
+use super::xchacha20poly1305::{Key, Header, Push, Pull, Tag,
+                               ABYTES, HEADERBYTES};
+use crypto::io_util::{invalid, read_full};
+use std::iter::repeat;
+
+pub const MAGIC: [u8; 4] = [0x73, 0x6f, 0x53, 0x53]; // "soSS"
+pub const ALGORITHM_XCHACHA20POLY1305: u8 = 1;
+pub const DEFAULT_CHUNK_SIZE: usize = 65536;
+pub const MAX_CHUNK_SIZE: usize = 16777216;
+
+const PREFIXBYTES: usize = 9;
+
+/**
+ * `EncryptWriter` encrypts everything written to it and writes the
+ * ciphertext to the inner writer.
+ *
+ * `finish()` has to be called after the last write, otherwise the final
+ * chunk is missing and `DecryptReader` rejects the stream as truncated.
+ */
+pub struct EncryptWriter<W: Write> {
+    inner: W,
+    push: Push,
+    prefix: [u8; PREFIXBYTES],
+    chunk_size: usize,
+    buf: Vec<u8>,
+}
+
+impl<W: Write> EncryptWriter<W> {
+    /**
+     * `new()` writes the header of a new stream, encrypted with the secret
+     * key `k`, to `inner` and uses `DEFAULT_CHUNK_SIZE`.
+     */
+    pub fn new(inner: W, k: &Key) -> io::Result<EncryptWriter<W>> {
+        EncryptWriter::with_chunk_size(inner, k, DEFAULT_CHUNK_SIZE)
+    }
+
+    /**
+     * `with_chunk_size()` is like `new()`, but splits the plaintext into
+     * chunks of `chunk_size` bytes, which has to be between 1 and
+     * `MAX_CHUNK_SIZE`.
+     */
+    pub fn with_chunk_size(mut inner: W,
+                           k: &Key,
+                           chunk_size: usize) -> io::Result<EncryptWriter<W>> {
+        if chunk_size == 0 || chunk_size > MAX_CHUNK_SIZE {
+            return Err(invalid("invalid chunk size"))
+        }
+        let (push, Header(header)) = Push::init(k);
+        let prefix = [MAGIC[0], MAGIC[1], MAGIC[2], MAGIC[3],
+                      ALGORITHM_XCHACHA20POLY1305,
+                      (chunk_size >> 24) as u8, (chunk_size >> 16) as u8,
+                      (chunk_size >> 8) as u8, chunk_size as u8];
+        try!(inner.write_all(&prefix));
+        try!(inner.write_all(&header));
+        Ok(EncryptWriter {
+            inner: inner,
+            push: push,
+            prefix: prefix,
+            chunk_size: chunk_size,
+            buf: Vec::with_capacity(chunk_size),
+        })
+    }
+
+    /**
+     * `finish()` encrypts the remaining buffered data as the final chunk,
+     * flushes the inner writer and returns it.
+     */
+    pub fn finish(mut self) -> io::Result<W> {
+        let c = self.push.push(&self.buf, Some(&self.prefix[..]), Tag::Final);
+        try!(self.inner.write_all(&c));
+        try!(self.inner.flush());
+        let EncryptWriter { inner, .. } = self;
+        Ok(inner)
+    }
+}
+
+impl<W: Write> Write for EncryptWriter<W> {
+    fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+        if self.buf.len() == self.chunk_size {
+            let c = self.push.push(&self.buf, Some(&self.prefix[..]), Tag::Message);
+            try!(self.inner.write_all(&c));
+            self.buf.clear();
+        }
+        let n = if data.len() > self.chunk_size - self.buf.len() {
+            self.chunk_size - self.buf.len()
+        } else {
+            data.len()
+        };
+        self.buf.push_all(&data[..n]);
+        Ok(n)
+    }
+
+    /**
+     * `flush()` flushes the inner writer. Data of an incomplete chunk stays
+     * buffered until the chunk is full or `finish()` is called.
+     */
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/**
+ * `DecryptReader` reads a stream written by `EncryptWriter` from the inner
+ * reader and returns the decrypted data.
+ */
+pub struct DecryptReader<R: Read> {
+    inner: R,
+    pull: Pull,
+    prefix: [u8; PREFIXBYTES],
+    chunk: Vec<u8>,
+    buf: Vec<u8>,
+    pos: usize,
+}
+
+impl<R: Read> DecryptReader<R> {
+    /**
+     * `new()` reads and checks the header of a stream from `inner` and
+     * prepares decrypting it with the secret key `k`.
+     */
+    pub fn new(mut inner: R, k: &Key) -> io::Result<DecryptReader<R>> {
+        let mut prefix = [0; PREFIXBYTES];
+        let mut header = [0; HEADERBYTES];
+        if try!(read_full(&mut inner, &mut prefix)) < PREFIXBYTES ||
+           try!(read_full(&mut inner, &mut header)) < HEADERBYTES {
+            return Err(invalid("stream header truncated"))
+        }
+        if &prefix[..4] != &MAGIC[..] {
+            return Err(invalid("not an encrypted stream"))
+        }
+        if prefix[4] != ALGORITHM_XCHACHA20POLY1305 {
+            return Err(invalid("unsupported algorithm"))
+        }
+        let chunk_size = ((prefix[5] as usize) << 24) |
+                         ((prefix[6] as usize) << 16) |
+                         ((prefix[7] as usize) << 8) |
+                         (prefix[8] as usize);
+        if chunk_size == 0 || chunk_size > MAX_CHUNK_SIZE {
+            return Err(invalid("invalid chunk size"))
+        }
+        Ok(DecryptReader {
+            inner: inner,
+            pull: Pull::init(&Header(header), k),
+            prefix: prefix,
+            chunk: repeat(0u8).take(chunk_size + ABYTES).collect(),
+            buf: Vec::new(),
+            pos: 0,
+        })
+    }
+
+    /**
+     * `into_inner()` returns the inner reader
+     */
+    pub fn into_inner(self) -> R {
+        let DecryptReader { inner, .. } = self;
+        inner
+    }
+
+    fn next_chunk(&mut self) -> io::Result<()> {
+        let n = try!(read_full(&mut self.inner, &mut self.chunk));
+        let (m, tag) = match self.pull.pull(&self.chunk[..n], Some(&self.prefix[..])) {
+            Some(r) => r,
+            None if n == 0 => return Err(invalid("stream truncated")),
+            None => return Err(invalid("chunk failed verification"))
+        };
+        match tag {
+            Tag::Final => {
+                let mut b = [0u8; 1];
+                if try!(read_full(&mut self.inner, &mut b)) != 0 {
+                    return Err(invalid("data after final chunk"))
+                }
+            }
+            Tag::Message if n == self.chunk.len() => {}
+            Tag::Message => return Err(invalid("stream truncated")),
+            _ => return Err(invalid("unexpected chunk tag"))
+        }
+        self.buf = m;
+        self.pos = 0;
+        Ok(())
+    }
+}
+
+impl<R: Read> Read for DecryptReader<R> {
+    fn read(&mut self, out: &mut [u8]) -> io::Result<usize> {
+        while self.pos == self.buf.len() {
+            if self.pull.is_finalized() {
+                return Ok(0)
+            }
+            try!(self.next_chunk());
+        }
+        let mut n = 0;
+        for (o, b) in out.iter_mut().zip(self.buf[self.pos..].iter()) {
+            *o = *b;
+            n += 1;
+        }
+        self.pos += n;
+        Ok(n)
+    }
+}
+
+#[cfg(test)]
+fn encrypt(m: &[u8], k: &Key, chunk_size: usize) -> Vec<u8> {
+    let mut w = EncryptWriter::with_chunk_size(Vec::new(), k, chunk_size).unwrap();
+    w.write_all(m).unwrap();
+    w.finish().unwrap()
+}
+
+#[cfg(test)]
+fn decrypt(c: &[u8], k: &Key) -> io::Result<Vec<u8>> {
+    use crypto::io_util::read_all;
+    read_all(DecryptReader::new(c, k))
+}
+
+#[test]
+fn test_encrypt_decrypt() {
+    use crypto::io_util::check_round_trip;
+    use super::xchacha20poly1305::gen_key;
+    check_round_trip(PREFIXBYTES + HEADERBYTES, ABYTES, |m, chunk_size| {
+        let k = gen_key();
+        let c = encrypt(m, &k, chunk_size);
+        let m2 = decrypt(&c, &k).unwrap();
+        (c, m2)
+    });
+}
+
+#[test]
+fn test_default_chunk_size() {
+    use randombytes::randombytes;
+    use super::xchacha20poly1305::gen_key;
+    let k = gen_key();
+    let m = randombytes(DEFAULT_CHUNK_SIZE * 2 + 100);
+    let mut w = EncryptWriter::new(Vec::new(), &k).unwrap();
+    for part in m.chunks(1000) {
+        w.write_all(part).unwrap();
+    }
+    let c = w.finish().unwrap();
+    assert!(decrypt(&c, &k).unwrap() == m);
+}
+
+#[test]
+fn test_truncation() {
+    use randombytes::randombytes;
+    use super::xchacha20poly1305::gen_key;
+    let k = gen_key();
+    let chunk_size = 16;
+    let m = randombytes(5 * chunk_size);
+    let c = encrypt(&m, &k, chunk_size);
+    for i in (0..c.len()) {
+        assert!(decrypt(&c[..i], &k).is_err());
+    }
+    // a writer that has not been finished lacks the final chunk
+    let mut w = EncryptWriter::with_chunk_size(Vec::new(), &k, chunk_size).unwrap();
+    w.write_all(&m).unwrap();
+    w.write_all(b"x").unwrap();
+    let EncryptWriter { inner: c, .. } = w;
+    assert!(decrypt(&c, &k).is_err());
+}
+
+#[test]
+fn test_tamper() {
+    use randombytes::randombytes;
+    use super::xchacha20poly1305::gen_key;
+    let k = gen_key();
+    let m = randombytes(100);
+    let mut c = encrypt(&m, &k, 32);
+    for i in (0..c.len()) {
+        c[i] ^= 0x20;
+        assert!(decrypt(&c, &k).is_err());
+        c[i] ^= 0x20;
+    }
+    let mut c2 = c.clone();
+    c2.push(0);
+    assert!(decrypt(&c2, &k).is_err());
+    assert!(decrypt(&c, &gen_key()).is_err());
+    assert!(decrypt(&c, &k).unwrap() == m);
+}
+
+#[test]
+fn test_invalid_chunk_size() {
+    use super::xchacha20poly1305::gen_key;
+    let k = gen_key();
+    assert!(EncryptWriter::with_chunk_size(Vec::new(), &k, 0).is_err());
+    assert!(EncryptWriter::with_chunk_size(Vec::new(), &k,
+                                           MAX_CHUNK_SIZE + 1).is_err());
+}
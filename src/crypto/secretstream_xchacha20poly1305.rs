@@ -0,0 +1,429 @@
+/*!
+`crypto_secretstream_xchacha20poly1305`, authenticated encryption of a
+sequence of chunks using XChaCha20 and Poly1305.
+*/
+use ffi;
+use libc::c_ulonglong;
+use std::intrinsics::volatile_set_memory;
+use std::iter::repeat;
+use std::mem;
+use std::ops::{Index, Range, RangeFrom, RangeFull, RangeTo};
+use std::ptr;
+use randombytes::randombytes_into;
+
+pub const KEYBYTES: usize = ffi::crypto_secretstream_xchacha20poly1305_KEYBYTES;
+pub const HEADERBYTES: usize = ffi::crypto_secretstream_xchacha20poly1305_HEADERBYTES;
+pub const ABYTES: usize = ffi::crypto_secretstream_xchacha20poly1305_ABYTES;
+
+/**
+ * `Key` for the stream
+ *
+ * When a `Key` goes out of scope its contents
+ * will be zeroed out
+ */
+pub struct Key(pub [u8; KEYBYTES]);
+
+newtype_drop!(Key);
+newtype_clone!(Key);
+newtype_impl!(Key, KEYBYTES);
+
+/**
+ * `Header` of the stream
+ *
+ * The header is generated by `Push::init()` and has to be sent to the
+ * receiver, which passes it to `Pull::init()`. It does not need to be kept
+ * secret.
+ */
+#[derive(Copy)]
+pub struct Header(pub [u8; HEADERBYTES]);
+
+newtype_clone!(Header);
+newtype_impl!(Header, HEADERBYTES);
+
+/**
+ * `Tag` attached to each encrypted chunk
+ */
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum Tag {
+    /// A regular chunk
+    Message,
+    /// The end of a set of chunks that belong together
+    Push,
+    /// Derive a new key after this chunk
+    Rekey,
+    /// The last chunk of the stream
+    Final,
+}
+
+impl Tag {
+    fn to_u8(self) -> u8 {
+        match self {
+            Tag::Message => ffi::crypto_secretstream_xchacha20poly1305_TAG_MESSAGE,
+            Tag::Push => ffi::crypto_secretstream_xchacha20poly1305_TAG_PUSH,
+            Tag::Rekey => ffi::crypto_secretstream_xchacha20poly1305_TAG_REKEY,
+            Tag::Final => ffi::crypto_secretstream_xchacha20poly1305_TAG_FINAL,
+        }
+    }
+
+    fn from_u8(tag: u8) -> Option<Tag> {
+        match tag {
+            ffi::crypto_secretstream_xchacha20poly1305_TAG_MESSAGE => Some(Tag::Message),
+            ffi::crypto_secretstream_xchacha20poly1305_TAG_PUSH => Some(Tag::Push),
+            ffi::crypto_secretstream_xchacha20poly1305_TAG_REKEY => Some(Tag::Rekey),
+            ffi::crypto_secretstream_xchacha20poly1305_TAG_FINAL => Some(Tag::Final),
+            _ => None
+        }
+    }
+}
+
+/**
+ * `gen_key()` randomly generates a secret key
+ *
+ * THREAD SAFETY: `gen_key()` is thread-safe provided that you have
+ * called `sodiumoxide::init()` once before using any other function
+ * from sodiumoxide.
+ */
+pub fn gen_key() -> Key {
+    let mut key = [0; KEYBYTES];
+    randombytes_into(&mut key);
+    Key(key)
+}
+
+fn ad_ptr_len(ad: Option<&[u8]>) -> (*const u8, c_ulonglong) {
+    match ad {
+        Some(ad) => (ad.as_ptr(), ad.len() as c_ulonglong),
+        None => (ptr::null(), 0)
+    }
+}
+
+fn wipe(st: &mut ffi::crypto_secretstream_xchacha20poly1305_state) {
+    unsafe {
+        volatile_set_memory(st.k.as_mut_ptr(), 0, st.k.len());
+        volatile_set_memory(st.nonce.as_mut_ptr(), 0, st.nonce.len());
+    }
+}
+
+/**
+ * `Push` encrypts a stream of chunks
+ *
+ * When a `Push` goes out of scope its contents will be zeroed out
+ */
+pub struct Push {
+    st: ffi::crypto_secretstream_xchacha20poly1305_state,
+}
+
+impl Push {
+    /**
+     * `init()` initializes a new stream using the secret key `k`.
+     * It returns the `Push` state and the `Header` that has to be passed to
+     * `Pull::init()` by the receiver.
+     *
+     * THREAD SAFETY: `Push::init()` is thread-safe provided that you have
+     * called `sodiumoxide::init()` once before using any other function
+     * from sodiumoxide.
+     */
+    pub fn init(&Key(ref k): &Key) -> (Push, Header) {
+        let mut push = Push { st: unsafe { mem::zeroed() } };
+        let mut header = [0; HEADERBYTES];
+        unsafe {
+            ffi::crypto_secretstream_xchacha20poly1305_init_push(&mut push.st,
+                                                                 &mut header,
+                                                                 k);
+        }
+        (push, Header(header))
+    }
+
+    /**
+     * `push()` encrypts and authenticates the next chunk `m` of the stream
+     * together with optional associated data `ad` and the tag `tag`.
+     * It returns the encrypted chunk, which is `ABYTES` longer than `m`.
+     *
+     * No chunks should be pushed after a chunk with `Tag::Final`.
+     */
+    pub fn push(&mut self, m: &[u8], ad: Option<&[u8]>, tag: Tag) -> Vec<u8> {
+        let (ad_p, ad_len) = ad_ptr_len(ad);
+        let mut c: Vec<u8> = repeat(0u8).take(m.len() + ABYTES).collect();
+        unsafe {
+            ffi::crypto_secretstream_xchacha20poly1305_push(&mut self.st,
+                                                            c.as_mut_ptr(),
+                                                            ptr::null_mut(),
+                                                            m.as_ptr(),
+                                                            m.len() as c_ulonglong,
+                                                            ad_p,
+                                                            ad_len,
+                                                            tag.to_u8());
+        }
+        c
+    }
+
+    /**
+     * `rekey()` explicitly derives a new key for the following chunks,
+     * without telling the receiver. The receiver has to call
+     * `Pull::rekey()` at the same position in the stream.
+     */
+    pub fn rekey(&mut self) {
+        unsafe {
+            ffi::crypto_secretstream_xchacha20poly1305_rekey(&mut self.st);
+        }
+    }
+}
+
+impl Drop for Push {
+    fn drop(&mut self) {
+        wipe(&mut self.st);
+    }
+}
+
+/**
+ * `Pull` decrypts and verifies a stream of chunks
+ *
+ * When a `Pull` goes out of scope its contents will be zeroed out
+ */
+pub struct Pull {
+    st: ffi::crypto_secretstream_xchacha20poly1305_state,
+    finalized: bool,
+}
+
+impl Pull {
+    /**
+     * `init()` initializes the decryption of a stream with the `Header`
+     * `header` using the secret key `k`.
+     */
+    pub fn init(&Header(ref header): &Header, &Key(ref k): &Key) -> Pull {
+        let mut pull = Pull { st: unsafe { mem::zeroed() }, finalized: false };
+        unsafe {
+            ffi::crypto_secretstream_xchacha20poly1305_init_pull(&mut pull.st,
+                                                                 header,
+                                                                 k);
+        }
+        pull
+    }
+
+    /**
+     * `pull()` verifies and decrypts the next encrypted chunk `c` of the
+     * stream using the optional associated data `ad`.
+     * It returns the decrypted chunk and its `Tag`.
+     *
+     * If the chunk fails verification, or if the stream has already been
+     * finalized, `pull()` returns `None`. A failed chunk does not advance the
+     * stream.
+     */
+    pub fn pull(&mut self, c: &[u8], ad: Option<&[u8]>) -> Option<(Vec<u8>, Tag)> {
+        if self.finalized || c.len() < ABYTES {
+            return None
+        }
+        let (ad_p, ad_len) = ad_ptr_len(ad);
+        let mut m: Vec<u8> = repeat(0u8).take(c.len() - ABYTES).collect();
+        let mut tag = 0u8;
+        let ret = unsafe {
+            ffi::crypto_secretstream_xchacha20poly1305_pull(&mut self.st,
+                                                            m.as_mut_ptr(),
+                                                            ptr::null_mut(),
+                                                            &mut tag,
+                                                            c.as_ptr(),
+                                                            c.len() as c_ulonglong,
+                                                            ad_p,
+                                                            ad_len)
+        };
+        if ret != 0 {
+            return None
+        }
+        let tag = match Tag::from_u8(tag) {
+            Some(tag) => tag,
+            None => return None
+        };
+        if tag == Tag::Final {
+            self.finalized = true;
+        }
+        Some((m, tag))
+    }
+
+    /**
+     * `rekey()` explicitly derives a new key for the following chunks, at
+     * the position in the stream where the sender called `Push::rekey()`.
+     */
+    pub fn rekey(&mut self) {
+        unsafe {
+            ffi::crypto_secretstream_xchacha20poly1305_rekey(&mut self.st);
+        }
+    }
+
+    /**
+     * `is_finalized()` returns `true` once a chunk with `Tag::Final` has been
+     * pulled. If the input ends before that, the stream has been truncated.
+     */
+    pub fn is_finalized(&self) -> bool {
+        self.finalized
+    }
+}
+
+impl Drop for Pull {
+    fn drop(&mut self) {
+        wipe(&mut self.st);
+    }
+}
+
+#[test]
+fn test_vector_pull() {
+    // generated with crypto_secretstream_xchacha20poly1305_push() from
+    // libsodium
+    let key = Key([0x00,0x01,0x02,0x03,0x04,0x05,0x06,0x07
+                  ,0x08,0x09,0x0a,0x0b,0x0c,0x0d,0x0e,0x0f
+                  ,0x10,0x11,0x12,0x13,0x14,0x15,0x16,0x17
+                  ,0x18,0x19,0x1a,0x1b,0x1c,0x1d,0x1e,0x1f]);
+    let header = Header([0xe0,0x30,0x9f,0x54,0x88,0xc1,0xe9,0x0b
+                        ,0x2a,0xb9,0x5c,0xb7,0x32,0x14,0x2d,0xd7
+                        ,0x4a,0x70,0xc2,0x07,0xab,0x8d,0x65,0x3b]);
+    let c1 = [0x3f,0x31,0x3b,0x32,0xa6,0x3d,0xf9,0x21
+             ,0x97,0xc9,0x30,0x72,0xda,0x6f,0x12,0x1e
+             ,0x2f,0xe7,0x73,0x68,0xdf,0x57,0x74,0x57
+             ,0x44,0x6a,0x59,0x47,0x11,0x46,0x01,0xf2
+             ,0x4b,0x72,0x19,0x60,0x4c,0xfb,0xd3,0x3b
+             ,0x85,0x0e];
+    let c2 = [0xc8,0x21,0xea,0xf4,0x68,0x06,0x7c,0x76
+             ,0x94,0xc9,0x76,0x9e,0xc0,0xcd,0xfc,0x90
+             ,0x2f,0x33,0xcf,0x0d,0x05,0x78,0x35,0x93
+             ,0x5c,0x4c,0x47];
+    let c3 = [0x66,0x1c,0x31,0x39,0xd3,0x8a,0x0a,0x87
+             ,0xb3,0xf5,0x5d,0xec,0x41,0x3a,0x39,0x87
+             ,0x1e,0xb5,0x42,0x47,0x4b,0x21,0xb5,0xf2
+             ,0xe7,0x90,0x53,0x50,0x7c,0x45,0x17];
+    let mut pull = Pull::init(&header, &key);
+    let (m1, t1) = pull.pull(&c1, None).unwrap();
+    assert!(&m1[..] == &b"Arbitrary data to encrypt"[..]);
+    assert!(t1 == Tag::Message);
+    let (m2, t2) = pull.pull(&c2, Some(&b"chunk 2"[..])).unwrap();
+    assert!(&m2[..] == &b"split into"[..]);
+    assert!(t2 == Tag::Rekey);
+    assert!(!pull.is_finalized());
+    let (m3, t3) = pull.pull(&c3, None).unwrap();
+    assert!(&m3[..] == &b"three messages"[..]);
+    assert!(t3 == Tag::Final);
+    assert!(pull.is_finalized());
+}
+
+#[test]
+fn test_push_pull() {
+    use randombytes::randombytes;
+    let tags = [Tag::Message, Tag::Push, Tag::Rekey];
+    for i in (0..64us) {
+        let k = gen_key();
+        let (mut push, header) = Push::init(&k);
+        let ms: Vec<Vec<u8>> = (0..i).map(|j| randombytes(j * 7)).collect();
+        let ad = randombytes(i);
+        let mut cs = Vec::new();
+        for (j, m) in ms.iter().enumerate() {
+            cs.push(push.push(&m, Some(&ad[..j]), tags[j % 3]));
+        }
+        let cfinal = push.push(b"", None, Tag::Final);
+
+        let mut pull = Pull::init(&header, &k);
+        for (j, (m, c)) in ms.iter().zip(cs.iter()).enumerate() {
+            assert!(c.len() == m.len() + ABYTES);
+            let (m2, tag) = pull.pull(&c, Some(&ad[..j])).unwrap();
+            assert!(m2 == *m);
+            assert!(tag == tags[j % 3]);
+        }
+        assert!(!pull.is_finalized());
+        let (m2, tag) = pull.pull(&cfinal, None).unwrap();
+        assert!(m2.len() == 0);
+        assert!(tag == Tag::Final);
+        assert!(pull.is_finalized());
+    }
+}
+
+#[test]
+fn test_pull_tamper() {
+    use randombytes::randombytes;
+    for i in (0..32us) {
+        let k = gen_key();
+        let (mut push, header) = Push::init(&k);
+        let m = randombytes(i);
+        let mut c = push.push(&m, Some(&b"ad"[..]), Tag::Message);
+        for j in (0..c.len()) {
+            c[j] ^= 0x20;
+            let mut pull = Pull::init(&header, &k);
+            assert!(pull.pull(&c, Some(&b"ad"[..])).is_none());
+            c[j] ^= 0x20;
+        }
+        let mut pull = Pull::init(&header, &k);
+        assert!(pull.pull(&c, Some(&b"da"[..])).is_none());
+        assert!(pull.pull(&c, None).is_none());
+        // failed chunks don't advance the stream
+        assert!(pull.pull(&c, Some(&b"ad"[..])).unwrap().0 == m);
+    }
+}
+
+#[test]
+fn test_pull_reorder_and_replay() {
+    let k = gen_key();
+    let (mut push, header) = Push::init(&k);
+    let c1 = push.push(b"first", None, Tag::Message);
+    let c2 = push.push(b"second", None, Tag::Message);
+    let c3 = push.push(b"third", None, Tag::Final);
+
+    let mut pull = Pull::init(&header, &k);
+    assert!(pull.pull(&c2, None).is_none());
+    assert!(pull.pull(&c1, None).is_some());
+    assert!(pull.pull(&c1, None).is_none());
+    assert!(pull.pull(&c3, None).is_none());
+    assert!(pull.pull(&c2, None).is_some());
+    assert!(pull.pull(&c3, None).is_some());
+    assert!(pull.is_finalized());
+    // nothing can be pulled after the final chunk
+    assert!(pull.pull(&c3, None).is_none());
+}
+
+#[test]
+fn test_wrong_key_or_header() {
+    let k = gen_key();
+    let (mut push, header) = Push::init(&k);
+    let c = push.push(b"message", None, Tag::Final);
+    let mut pull = Pull::init(&header, &gen_key());
+    assert!(pull.pull(&c, None).is_none());
+    let (_, other_header) = Push::init(&k);
+    let mut pull = Pull::init(&other_header, &k);
+    assert!(pull.pull(&c, None).is_none());
+    let mut pull = Pull::init(&header, &k);
+    assert!(pull.pull(&c[..ABYTES - 1], None).is_none());
+}
+
+#[test]
+fn test_explicit_rekey() {
+    let k = gen_key();
+    let (mut push, header) = Push::init(&k);
+    let c1 = push.push(b"before rekey", None, Tag::Message);
+    push.rekey();
+    let c2 = push.push(b"after rekey", None, Tag::Final);
+
+    let mut pull = Pull::init(&header, &k);
+    assert!(pull.pull(&c1, None).is_some());
+    assert!(pull.pull(&c2, None).is_none());
+    pull.rekey();
+    let (m2, _) = pull.pull(&c2, None).unwrap();
+    assert!(&m2[..] == &b"after rekey"[..]);
+}
+
+#[cfg(test)]
+mod bench {
+    extern crate test;
+    use randombytes::randombytes;
+    use super::*;
+
+    const BENCH_SIZES: [usize; 14] = [0, 1, 2, 4, 8, 16, 32, 64,
+                                      128, 256, 512, 1024, 2048, 4096];
+
+    #[bench]
+    fn bench_push(b: &mut test::Bencher) {
+        let k = gen_key();
+        let (mut push, _) = Push::init(&k);
+        let ms: Vec<Vec<u8>> = BENCH_SIZES.iter().map(|s| {
+            randombytes(*s)
+        }).collect();
+        b.iter(|| {
+            for m in ms.iter() {
+                push.push(&m, None, Tag::Message);
+            }
+        });
+    }
+}
@@ -9,9 +9,18 @@ SHA-3 competition will improve the situation.
 */
 #[cfg(test)]
 extern crate "rustc-serialize" as rustc_serialize;
-use ffi::{crypto_hash_sha512, crypto_hash_sha512_BYTES};
+use ffi::{crypto_hash_sha512,
+          crypto_hash_sha512_state,
+          crypto_hash_sha512_init,
+          crypto_hash_sha512_update,
+          crypto_hash_sha512_final,
+          crypto_hash_sha512_BYTES};
 
 hash_module!(crypto_hash_sha512,
+             crypto_hash_sha512_state,
+             crypto_hash_sha512_init,
+             crypto_hash_sha512_update,
+             crypto_hash_sha512_final,
              crypto_hash_sha512_BYTES,
              128);
 
@@ -56,6 +65,8 @@ fn test_nist_vector(filename: &str) {
             let md = line3[5..].from_hex().unwrap();
             let Digest(digest) = hash(msg);
             assert!(&digest[] == &md[]);
+            let Digest(digest) = hash_in_random_chunks(msg);
+            assert!(&digest[] == &md[]);
         }
     }
 }
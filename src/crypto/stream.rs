@@ -51,6 +51,12 @@ risk of collision. Callers who are unable to count 1, 2, 3..., and who insist
 on using these primitives, are advised to use a randomly derived key for each
 message.
 
+# Initial block counter
+`salsa20` and `xsalsa20` (and therefore the default primitive) additionally
+provide `stream_xor_ic()`, which starts at an arbitrary block of the key
+stream, and `Cipher`, which encrypts successive chunks of a message and can
+seek to an arbitrary byte offset.
+
 */
 pub use self::xsalsa20::*;
 #[path="stream_macros.rs"]
@@ -189,3 +189,191 @@ mod bench {
 }
 
 ));
+
+/* `stream_ic_module!` adds `stream_xor_ic()` and `Cipher` to a module that
+   has been created with `stream_module!`, for primitives that support an
+   initial block counter. */
+macro_rules! stream_ic_module (($xor_ic_name:ident) => (
+
+pub const BLOCKBYTES: usize = 64;
+
+/**
+ * `stream_xor_ic()` encrypts a message `m` using a secret key `k` and a nonce
+ * `n` like `stream_xor()`, but starts with block `ic` of the key stream
+ * instead of block 0. A block is `BLOCKBYTES` long, so the result equals the
+ * part of the output of `stream_xor()` that starts at byte `ic * BLOCKBYTES`.
+ */
+pub fn stream_xor_ic(m: &[u8],
+                     &Nonce(ref n): &Nonce,
+                     ic: u64,
+                     &Key(ref k): &Key) -> Vec<u8> {
+    unsafe {
+        let mut c: Vec<u8> = repeat(0u8).take(m.len()).collect();
+        $xor_ic_name(c.as_mut_ptr(),
+                     m.as_ptr(),
+                     m.len() as c_ulonglong,
+                     n,
+                     ic,
+                     k);
+        c
+    }
+}
+
+/**
+ * `stream_xor_ic_inplace()` is like `stream_xor_ic()`, but encrypts the
+ * message `m` in place.
+ */
+pub fn stream_xor_ic_inplace(m: &mut [u8],
+                             &Nonce(ref n): &Nonce,
+                             ic: u64,
+                             &Key(ref k): &Key) {
+    unsafe {
+        $xor_ic_name(m.as_mut_ptr(),
+                     m.as_ptr(),
+                     m.len() as c_ulonglong,
+                     n,
+                     ic,
+                     k);
+    }
+}
+
+/**
+ * `Cipher` encrypts successive chunks of a message with the key stream of a
+ * secret key and a nonce, keeping track of the position in the key stream.
+ *
+ * Encrypting a message in several chunks gives the same result as
+ * encrypting it with a single call to `stream_xor()`. `seek()` moves to an
+ * arbitrary byte offset, e.g. to decrypt the middle of a large message.
+ */
+pub struct Cipher {
+    key: Key,
+    nonce: Nonce,
+    pos: u64,
+}
+
+impl Cipher {
+    /**
+     * `new()` creates a `Cipher` for the secret key `k` and the nonce `n`,
+     * positioned at the start of the key stream.
+     */
+    pub fn new(n: &Nonce, k: &Key) -> Cipher {
+        Cipher { key: k.clone(), nonce: *n, pos: 0 }
+    }
+
+    /**
+     * `position()` returns the current byte offset in the key stream
+     */
+    pub fn position(&self) -> u64 {
+        self.pos
+    }
+
+    /**
+     * `seek()` moves to the byte offset `pos` in the key stream
+     */
+    pub fn seek(&mut self, pos: u64) {
+        self.pos = pos;
+    }
+
+    /**
+     * `xor()` encrypts or decrypts the next chunk `m` and returns the result
+     */
+    pub fn xor(&mut self, m: &[u8]) -> Vec<u8> {
+        let mut c = m.to_vec();
+        self.xor_inplace(&mut c);
+        c
+    }
+
+    /**
+     * `xor_inplace()` encrypts or decrypts the next chunk `m` in place
+     */
+    pub fn xor_inplace(&mut self, m: &mut [u8]) {
+        let block = self.pos / BLOCKBYTES as u64;
+        let offset = (self.pos % BLOCKBYTES as u64) as usize;
+        let mut start = 0;
+        if offset != 0 {
+            // the first block is only used partially
+            let len = if m.len() < BLOCKBYTES - offset { m.len() }
+                      else { BLOCKBYTES - offset };
+            let mut buf = [0u8; BLOCKBYTES];
+            for (b, &mi) in buf[offset..].iter_mut().zip(m[..len].iter()) {
+                *b = mi;
+            }
+            stream_xor_ic_inplace(&mut buf, &self.nonce, block, &self.key);
+            for (mi, &b) in m[..len].iter_mut().zip(buf[offset..].iter()) {
+                *mi = b;
+            }
+            unsafe {
+                volatile_set_memory(buf.as_mut_ptr(), 0, BLOCKBYTES);
+            }
+            start = len;
+        }
+        let next_block = if offset != 0 { block + 1 } else { block };
+        stream_xor_ic_inplace(&mut m[start..], &self.nonce, next_block,
+                              &self.key);
+        self.pos += m.len() as u64;
+    }
+}
+
+#[test]
+fn test_stream_xor_ic() {
+    use randombytes::randombytes;
+    for i in (0..256us) {
+        let k = gen_key();
+        let n = gen_nonce();
+        let ic = (i % 8) as u64;
+        let m = randombytes(i);
+        let mut c = stream_xor(&m, &n, &k);
+        let mut m2: Vec<u8> = repeat(0u8).take(ic as usize * BLOCKBYTES).collect();
+        m2.push_all(&m);
+        let c2 = stream_xor(&m2, &n, &k);
+        let c_ic = stream_xor_ic(&m, &n, ic, &k);
+        assert!(&c_ic[..] == &c2[ic as usize * BLOCKBYTES..]);
+        stream_xor_ic_inplace(&mut c, &n, 0, &k);
+        assert!(c == m);
+    }
+}
+
+#[test]
+fn test_cipher_chunks() {
+    use randombytes::randombytes;
+    for i in (0..512us) {
+        let k = gen_key();
+        let n = gen_nonce();
+        let m = randombytes(i);
+        let c_expected = stream_xor(&m, &n, &k);
+        let mut cipher = Cipher::new(&n, &k);
+        let mut c = Vec::new();
+        let mut pos = 0;
+        while pos < m.len() {
+            let chunk = randombytes(1)[0] as usize % (2 * BLOCKBYTES);
+            let end = if pos + chunk > m.len() { m.len() } else { pos + chunk };
+            c.push_all(&cipher.xor(&m[pos..end]));
+            pos = end;
+        }
+        assert!(cipher.position() == m.len() as u64);
+        assert!(c == c_expected);
+    }
+}
+
+#[test]
+fn test_cipher_seek() {
+    use randombytes::randombytes;
+    let k = gen_key();
+    let n = gen_nonce();
+    let m = randombytes(2000);
+    let c = stream_xor(&m, &n, &k);
+    let mut cipher = Cipher::new(&n, &k);
+    for _ in (0..500us) {
+        let rnd = randombytes(3);
+        let start = ((rnd[0] as usize) << 8 | rnd[1] as usize) % m.len();
+        let len = rnd[2] as usize;
+        let end = if start + len > m.len() { m.len() } else { start + len };
+        cipher.seek(start as u64);
+        let mut part = c[start..end].to_vec();
+        cipher.xor_inplace(&mut part);
+        assert!(&part[..] == &m[start..end]);
+        assert!(cipher.position() == end as u64);
+    }
+}
+
+));
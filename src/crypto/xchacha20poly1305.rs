@@ -0,0 +1,61 @@
+/*!
+`crypto_secretbox_xchacha20poly1305`, a particular
+combination of XChaCha20 and Poly1305 which works like
+`crypto_secretbox_xsalsa20poly1305` with the Salsa20 stream cipher
+replaced by ChaCha20.
+
+This function is conjectured to meet the standard notions of privacy and
+authenticity.
+*/
+use ffi::{crypto_secretbox_xchacha20poly1305_easy,
+          crypto_secretbox_xchacha20poly1305_open_easy,
+          crypto_secretbox_xchacha20poly1305_detached,
+          crypto_secretbox_xchacha20poly1305_open_detached,
+          crypto_secretbox_xchacha20poly1305_KEYBYTES,
+          crypto_secretbox_xchacha20poly1305_NONCEBYTES,
+          crypto_secretbox_xchacha20poly1305_MACBYTES};
+
+secretbox_module!(crypto_secretbox_xchacha20poly1305_easy,
+                  crypto_secretbox_xchacha20poly1305_open_easy,
+                  crypto_secretbox_xchacha20poly1305_detached,
+                  crypto_secretbox_xchacha20poly1305_open_detached,
+                  crypto_secretbox_xchacha20poly1305_KEYBYTES,
+                  crypto_secretbox_xchacha20poly1305_NONCEBYTES,
+                  crypto_secretbox_xchacha20poly1305_MACBYTES);
+
+#[test]
+fn test_vector_1() {
+    // the key and nonce of the xsalsa20poly1305 test vector,
+    // checked against an independent implementation
+    let firstkey = Key([0x1b,0x27,0x55,0x64,0x73,0xe9,0x85,0xd4
+                       ,0x62,0xcd,0x51,0x19,0x7a,0x9a,0x46,0xc7
+                       ,0x60,0x09,0x54,0x9e,0xac,0x64,0x74,0xf2
+                       ,0x06,0xc4,0xee,0x08,0x44,0xf6,0x83,0x89]);
+    let nonce = Nonce([0x69,0x69,0x6e,0xe9,0x55,0xb6,0x2b,0x73
+                      ,0xcd,0x62,0xbd,0xa8,0x75,0xfc,0x73,0xd6
+                      ,0x82,0x19,0xe0,0x03,0x6b,0x7a,0x0b,0x37]);
+    let m = b"Ladies and Gentlemen of the class of 99: \
+              If I could offer you only one tip for the future, \
+              sunscreen would be it.".to_vec();
+    let c_expected = vec![0x93,0xa2,0x05,0x7c,0x3c,0xc3,0xe0,0x61
+                      ,0x23,0x42,0x62,0x77,0xb9,0x0c,0x00,0x01
+                      ,0x4d,0xec,0xc8,0xb5,0xdc,0x77,0x37,0xe9
+                      ,0x3d,0xc0,0x4d,0x8d,0x5f,0x60,0xde,0x82
+                      ,0x11,0x11,0xdf,0xf9,0x63,0x21,0xc9,0xda
+                      ,0xa1,0xe7,0x8f,0xa2,0x41,0x68,0x1f,0x7e
+                      ,0x95,0xe3,0xc9,0x11,0x71,0x3f,0xd9,0x09
+                      ,0x3a,0x9e,0x72,0xf2,0xbb,0x5a,0x36,0x0b
+                      ,0x66,0x2c,0xc5,0xd1,0xb2,0xf9,0xf2,0x36
+                      ,0x5c,0x48,0xb2,0x4b,0x69,0x9d,0xbe,0x3e
+                      ,0xfb,0x74,0x34,0x8f,0x32,0x3e,0x37,0x45
+                      ,0xfa,0x8b,0x34,0x93,0x73,0x33,0xd3,0xe7
+                      ,0xeb,0x3e,0xd7,0x92,0x63,0xd3,0x95,0xc9
+                      ,0xcd,0x33,0xdb,0xf8,0x66,0xc1,0x7d,0x42
+                      ,0x1b,0x78,0x1b,0xa0,0xe9,0x4c,0x1a,0x93
+                      ,0x88,0x7c,0x1a,0xa8,0x54,0x23,0xcf,0x24
+                      ,0x14];
+    let c = seal(&m, &nonce, &firstkey);
+    assert!(c == c_expected);
+    let m2 = open(&c, &nonce, &firstkey);
+    assert!(Some(m) == m2);
+}
@@ -0,0 +1,61 @@
+/*!
+`crypto_aead_xchacha20poly1305_ietf`, the IETF ChaCha20-Poly1305 construction
+extended with HChaCha20 to a 192-bit nonce, as specified in
+[XChaCha: eXtended-nonce ChaCha and AEAD_XChaCha20_Poly1305](https://tools.ietf.org/html/draft-irtf-cfrg-xchacha-03).
+
+The nonce is long enough that randomly generated nonces have negligible
+risk of collision, so `gen_nonce()` can safely be used for every message.
+*/
+#[cfg(test)]
+extern crate "rustc-serialize" as rustc_serialize;
+use ffi::{crypto_aead_xchacha20poly1305_ietf_encrypt,
+          crypto_aead_xchacha20poly1305_ietf_decrypt,
+          crypto_aead_xchacha20poly1305_ietf_encrypt_detached,
+          crypto_aead_xchacha20poly1305_ietf_decrypt_detached,
+          crypto_aead_xchacha20poly1305_ietf_KEYBYTES,
+          crypto_aead_xchacha20poly1305_ietf_NPUBBYTES,
+          crypto_aead_xchacha20poly1305_ietf_ABYTES};
+
+aead_module!(crypto_aead_xchacha20poly1305_ietf_encrypt,
+             crypto_aead_xchacha20poly1305_ietf_decrypt,
+             crypto_aead_xchacha20poly1305_ietf_encrypt_detached,
+             crypto_aead_xchacha20poly1305_ietf_decrypt_detached,
+             crypto_aead_xchacha20poly1305_ietf_KEYBYTES,
+             crypto_aead_xchacha20poly1305_ietf_NPUBBYTES,
+             crypto_aead_xchacha20poly1305_ietf_ABYTES,
+             true);
+
+#[test]
+fn test_vectors_xchacha() {
+    // test vector from draft-irtf-cfrg-xchacha-03, Appendix A.3.1
+    use self::rustc_serialize::hex::FromHex;
+    use std::old_io::BufferedReader;
+    use std::old_io::File;
+    use std::path::Path;
+
+    let p = &Path::new("testvectors/xchacha20poly1305_ietf.input");
+    let mut r = BufferedReader::new(File::open(p).unwrap());
+    loop {
+        let line = match r.read_line() {
+            Err(_) => break,
+            Ok(line) => line
+        };
+        let mut x = line.split(':');
+        let k = Key::from_slice(&x.next().unwrap().from_hex().unwrap()).unwrap();
+        let n = Nonce::from_slice(&x.next().unwrap().from_hex().unwrap()).unwrap();
+        let ad = x.next().unwrap().from_hex().unwrap();
+        let m = x.next().unwrap().from_hex().unwrap();
+        let c_expected = x.next().unwrap().from_hex().unwrap();
+        let c = seal(&m, Some(&ad[..]), &n, &k);
+        assert!(c == c_expected);
+        let m2 = open(&c, Some(&ad[..]), &n, &k);
+        assert!(Some(m.clone()) == m2);
+
+        let mut buf = m.clone();
+        let tag = seal_detached(buf.as_mut_slice(), Some(&ad[..]), &n, &k);
+        assert!(&buf[..] == &c_expected[..m.len()]);
+        assert!(&tag[..] == &c_expected[m.len()..]);
+        assert!(open_detached(buf.as_mut_slice(), Some(&ad[..]), &tag, &n, &k).is_ok());
+        assert!(buf == m);
+    }
+}
@@ -6,6 +6,7 @@ unpredictability.
 */
 use ffi::{crypto_stream_xsalsa20,
           crypto_stream_xsalsa20_xor,
+          crypto_stream_xsalsa20_xor_ic,
           crypto_stream_xsalsa20_KEYBYTES,
           crypto_stream_xsalsa20_NONCEBYTES};
 
@@ -14,6 +15,8 @@ stream_module!(crypto_stream_xsalsa20,
                crypto_stream_xsalsa20_KEYBYTES,
                crypto_stream_xsalsa20_NONCEBYTES);
 
+stream_ic_module!(crypto_stream_xsalsa20_xor_ic);
+
 #[test]
 fn test_vector_1() {
     // corresponding to tests/stream.c and tests/stream5.cpp from NaCl
@@ -6,137 +6,21 @@ combination of Salsa20 and Poly1305 specified in
 This function is conjectured to meet the standard notions of privacy and
 authenticity.
 */
-use ffi;
-use std::intrinsics::volatile_set_memory;
-use std::ops::{Index, Range, RangeFrom, RangeFull, RangeTo};
-use utils::marshal;
-use randombytes::randombytes_into;
-
-pub const KEYBYTES: usize = ffi::crypto_secretbox_xsalsa20poly1305_KEYBYTES;
-pub const NONCEBYTES: usize = ffi::crypto_secretbox_xsalsa20poly1305_NONCEBYTES;
-
-/**
- * `Key` for symmetric authenticated encryption
- *
- * When a `Key` goes out of scope its contents
- * will be zeroed out
- */
-pub struct Key(pub [u8; KEYBYTES]);
-
-newtype_drop!(Key);
-newtype_clone!(Key);
-newtype_impl!(Key, KEYBYTES);
-
-/**
- * `Nonce` for symmetric authenticated encryption
- */
-#[derive(Copy)]
-pub struct Nonce(pub [u8; NONCEBYTES]);
-
-newtype_clone!(Nonce);
-newtype_impl!(Nonce, NONCEBYTES);
-
-const ZEROBYTES: usize = 32;
-const BOXZEROBYTES: usize = 16;
-
-/**
- * `gen_key()` randomly generates a secret key
- *
- * THREAD SAFETY: `gen_key()` is thread-safe provided that you have
- * called `sodiumoxide::init()` once before using any other function
- * from sodiumoxide.
- */
-pub fn gen_key() -> Key {
-    let mut key = [0; KEYBYTES];
-    randombytes_into(&mut key);
-    Key(key)
-}
-
-/**
- * `gen_nonce()` randomly generates a nonce
- *
- * THREAD SAFETY: `gen_key()` is thread-safe provided that you have
- * called `sodiumoxide::init()` once before using any other function
- * from sodiumoxide.
- */
-pub fn gen_nonce() -> Nonce {
-    let mut nonce = [0; NONCEBYTES];
-    randombytes_into(&mut nonce);
-    Nonce(nonce)
-}
-
-/**
- * `seal()` encrypts and authenticates a message `m` using a secret key `k` and a
- * nonce `n`.  It returns a ciphertext `c`.
- */
-pub fn seal(m: &[u8],
-            &Nonce(ref n): &Nonce,
-            &Key(ref k): &Key) -> Vec<u8> {
-    let (c, _) = marshal(m, ZEROBYTES, BOXZEROBYTES, |dst, src, len| {
-        unsafe {
-            ffi::crypto_secretbox_xsalsa20poly1305(dst, src, len,
-                                                   n, k)
-        }
-    });
-    c
-}
-
-/**
- * `open()` verifies and decrypts a ciphertext `c` using a secret key `k` and a nonce `n`.
- * It returns a plaintext `Some(m)`.
- * If the ciphertext fails verification, `open()` returns `None`.
- */
-pub fn open(c: &[u8],
-            &Nonce(ref n): &Nonce,
-            &Key(ref k): &Key) -> Option<Vec<u8>> {
-    if c.len() < BOXZEROBYTES {
-        return None
-    }
-    let (m, ret) = marshal(c, BOXZEROBYTES, ZEROBYTES, |dst, src, len| {
-        unsafe {
-            ffi::crypto_secretbox_xsalsa20poly1305_open(dst,
-                                                        src,
-                                                        len,
-                                                        n,
-                                                        k)
-        }
-    });
-    if ret == 0 {
-        Some(m)
-    } else {
-        None
-    }
-}
-
-#[test]
-fn test_seal_open() {
-    use randombytes::randombytes;
-    for i in (0..256us) {
-        let k = gen_key();
-        let m = randombytes(i);
-        let n = gen_nonce();
-        let c = seal(&m, &n, &k);
-        let opened = open(&c, &n, &k);
-        assert!(Some(m) == opened);
-    }
-}
-
-#[test]
-fn test_seal_open_tamper() {
-    use randombytes::randombytes;
-    for i in (0..32us) {
-        let k = gen_key();
-        let m = randombytes(i);
-        let n = gen_nonce();
-        let mut cv = seal(&m, &n, &k);
-        let c = cv.as_mut_slice();
-        for i in (0..c.len()) {
-            c[i] ^= 0x20;
-            assert!(None == open(c, &n, &k));
-            c[i] ^= 0x20;
-        }
-    }
-}
+use ffi::{crypto_secretbox_easy,
+          crypto_secretbox_open_easy,
+          crypto_secretbox_detached,
+          crypto_secretbox_open_detached,
+          crypto_secretbox_xsalsa20poly1305_KEYBYTES,
+          crypto_secretbox_xsalsa20poly1305_NONCEBYTES,
+          crypto_secretbox_xsalsa20poly1305_MACBYTES};
+
+secretbox_module!(crypto_secretbox_easy,
+                  crypto_secretbox_open_easy,
+                  crypto_secretbox_detached,
+                  crypto_secretbox_open_detached,
+                  crypto_secretbox_xsalsa20poly1305_KEYBYTES,
+                  crypto_secretbox_xsalsa20poly1305_NONCEBYTES,
+                  crypto_secretbox_xsalsa20poly1305_MACBYTES);
 
 #[test]
 fn test_vector_1() {
@@ -189,27 +73,3 @@ fn test_vector_1() {
     let m2 = open(&c, &nonce, &firstkey);
     assert!(Some(m) == m2);
 }
-
-#[cfg(test)]
-mod bench {
-    extern crate test;
-    use randombytes::randombytes;
-    use super::*;
-
-    const BENCH_SIZES: [usize; 14] = [0, 1, 2, 4, 8, 16, 32, 64,
-                                      128, 256, 512, 1024, 2048, 4096];
-
-    #[bench]
-    fn bench_seal_open(b: &mut test::Bencher) {
-        let k = gen_key();
-        let n = gen_nonce();
-        let ms: Vec<Vec<u8>> = BENCH_SIZES.iter().map(|s| {
-            randombytes(*s)
-        }).collect();
-        b.iter(|| {
-            for m in ms.iter() {
-                open(&seal(&m, &n, &k), &n, &k).unwrap();
-            }
-        });
-    }
-}
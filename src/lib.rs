@@ -5,14 +5,26 @@ Sodium is a portable implementation of Dan Bernsteins [NaCl: Networking and
 Cryptography library](http://nacl.cr.yp.to)
 
 For most users, if you want public-key (asymmetric) cryptography you should use
-the functions in `crypto::asymmetricbox` for encryption/decryption.
+the functions in `crypto::asymmetricbox` for encryption/decryption. If the sender
+should stay anonymous and doesn't have a key pair of its own, use
+`crypto::sealedbox` instead.
 
 If you want secret-key (symmetric) cryptography you should be using the
 functions in `crypto::secretbox` for encryption/decryption.
 
+If you need to authenticate additional data along with the encrypted message
+you should use the functions in `crypto::aead`.
+
+If you want to encrypt a long stream of data, e.g. a file or a log, in chunks
+you should use `crypto::secretstream`.
+
 For public-key signatures you should use the functions in `crypto::sign` for
 signature creation and verification.
 
+If you want to store passwords or derive keys from passwords you should use
+the functions in `crypto::pwhash`, preferably `crypto::pwhash::argon2id13` for
+new applications.
+
 Unless you know what you're doing you most certainly don't want to use the
 functions in `crypto::scalarmult`, `crypto::stream`, `crypto::auth` and
 `crypto::onetimeauth`.
@@ -27,20 +39,31 @@ generation functions and the key-generation functions are thread-safe.
 # Public-key cryptography
  `crypto::asymmetricbox`
 
+ `crypto::sealedbox`
+
  `crypto::sign`
 
 # Secret-key cryptography
  `crypto::secretbox`
 
+ `crypto::aead`
+
+ `crypto::secretstream`
+
  `crypto::stream`
 
  `crypto::auth`
 
  `crypto::onetimeauth`
 
+# Password hashing
+ `crypto::pwhash`
+
 # Low-level functions
  `crypto::hash`
 
+ `crypto::generichash`
+
  `crypto::verify`
 
  `crypto::shorthash`
@@ -83,15 +106,21 @@ pub mod randombytes;
  * Cryptographic functions
  */
 pub mod crypto {
+    pub mod aead;
     pub mod asymmetricbox;
+    pub mod sealedbox;
     pub mod sign;
     pub mod scalarmult;
     pub mod auth;
     pub mod hash;
+    pub mod pwhash;
+    pub mod generichash;
     pub mod secretbox;
+    pub mod secretstream;
     pub mod onetimeauth;
     pub mod stream;
     pub mod shorthash;
     pub mod verify;
+    mod io_util;
 }
 